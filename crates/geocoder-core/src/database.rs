@@ -2,17 +2,90 @@
 //!
 //! Provides a high-level interface for querying SQLite geocoding shards.
 
+use std::cell::RefCell;
 use std::path::Path;
+use std::time::Instant;
 
+use rstar::{PointDistance, RTree, RTreeObject, AABB};
 use rusqlite::{Connection, OpenFlags};
 
 use crate::error::Result;
-use crate::query::{calculate_boosted_score, prepare_fts_query, SEARCH_DIVISIONS_SQL};
-use crate::types::{DivisionRow, GeocoderQuery, GeocoderResult};
+use crate::query::{
+    apply_location_bias, blend_importance, calculate_boosted_score, calculate_fuzzy_boosted_score,
+    haversine_km, jaro_winkler, prepare_fts_query, FUZZY_CANDIDATES_SQL, REVERSE_GEOCODE_SQL,
+    REVERSE_NEAREST_CANDIDATES_SQL, SEARCH_DIVISIONS_SQL,
+};
+use crate::pager::{open_paged_connection, PageReader};
+use crate::types::{
+    DivisionRow, GeocoderQuery, GeocoderResult, LocationBias, ReverseResult, SearchResponse,
+};
+
+/// Default cap on the number of enclosing divisions returned by
+/// [`Database::reverse`] when the caller doesn't specify one.
+const DEFAULT_REVERSE_LIMIT: usize = 10;
+
+/// Default candidate-count threshold under which `search_near` ranks by
+/// distance directly; above it, an R-tree nearest-neighbor walk is used
+/// instead of sorting the whole candidate set.
+pub const DEFAULT_PROXIMITY_RTREE_THRESHOLD: usize = 1000;
+
+/// A search candidate indexed for nearest-neighbor queries. Coordinates are
+/// stored as unit-sphere xyz so euclidean distance in the tree orders
+/// candidates the same way great-circle distance would.
+struct IndexedPoint {
+    index: usize,
+    xyz: [f64; 3],
+}
+
+impl RTreeObject for IndexedPoint {
+    type Envelope = AABB<[f64; 3]>;
+
+    fn envelope(&self) -> Self::Envelope {
+        AABB::from_point(self.xyz)
+    }
+}
+
+impl PointDistance for IndexedPoint {
+    fn distance_2(&self, point: &[f64; 3]) -> f64 {
+        let dx = self.xyz[0] - point[0];
+        let dy = self.xyz[1] - point[1];
+        let dz = self.xyz[2] - point[2];
+        dx * dx + dy * dy + dz * dz
+    }
+}
+
+fn lat_lon_to_unit_xyz(lat: f64, lon: f64) -> [f64; 3] {
+    let (lat_r, lon_r) = (lat.to_radians(), lon.to_radians());
+    [
+        lat_r.cos() * lon_r.cos(),
+        lat_r.cos() * lon_r.sin(),
+        lat_r.sin(),
+    ]
+}
+
+/// Fingerprint of a candidate set, used to decide whether a cached R-tree
+/// can be reused for a subsequent `search_near` call over the same results.
+fn fingerprint_candidates(candidates: &[GeocoderResult]) -> u64 {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = DefaultHasher::new();
+    for candidate in candidates {
+        candidate.gers_id.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+struct RTreeCacheEntry {
+    fingerprint: u64,
+    tree: RTree<IndexedPoint>,
+}
 
 /// A SQLite database connection for geocoding queries.
 pub struct Database {
     conn: Connection,
+    proximity_rtree_threshold: usize,
+    rtree_cache: RefCell<Option<RTreeCacheEntry>>,
 }
 
 impl Database {
@@ -30,7 +103,11 @@ impl Database {
              PRAGMA temp_store = MEMORY;",
         )?;
 
-        Ok(Self { conn })
+        Ok(Self {
+            conn,
+            proximity_rtree_threshold: DEFAULT_PROXIMITY_RTREE_THRESHOLD,
+            rtree_cache: RefCell::new(None),
+        })
     }
 
     /// Open a database from bytes (for WASM compatibility testing).
@@ -51,14 +128,43 @@ impl Database {
         Self::open(&temp_path)
     }
 
+    /// Open a database without downloading it whole: the header is fetched
+    /// up front to learn `page_size`/`page_count`, and every page after
+    /// that is fetched on demand through `reader` as SQLite's own index
+    /// traversal touches it - so a point lookup against a large shard costs
+    /// a handful of page reads rather than the whole file.
+    ///
+    /// Registers a uniquely-named VFS for the lifetime of the isolate -
+    /// `sqlite-vfs` (as of 0.2) exposes no `unregister`, so this is a known,
+    /// accepted per-call leak of one VFS table entry. Bounded in practice:
+    /// a paged `Database` is only ever used for a single paged reverse-geocode
+    /// or search attempt and is never cached (see `geocoder-worker`'s `DatabaseLru`),
+    /// so the leak tracks request volume, not shard count, and clears on
+    /// the next isolate recycle.
+    pub fn from_pager<R: PageReader + Clone + 'static>(reader: R) -> Result<Self> {
+        let vfs_name = format!("paged-vfs-{}", uuid_v4());
+        let conn = open_paged_connection(&vfs_name, reader)?;
+
+        Ok(Self {
+            conn,
+            proximity_rtree_threshold: DEFAULT_PROXIMITY_RTREE_THRESHOLD,
+            rtree_cache: RefCell::new(None),
+        })
+    }
+
     /// Search for divisions matching the query.
-    pub fn search(&self, query: &GeocoderQuery) -> Result<Vec<GeocoderResult>> {
+    pub fn search(&self, query: &GeocoderQuery) -> Result<SearchResponse> {
         let fts_query = prepare_fts_query(&query.text, query.autocomplete);
 
         if fts_query.is_empty() {
-            return Ok(vec![]);
+            return Ok(SearchResponse {
+                results: vec![],
+                degraded: false,
+            });
         }
 
+        let start = Instant::now();
+
         let mut stmt = self.conn.prepare_cached(SEARCH_DIVISIONS_SQL)?;
 
         // Fetch more results than requested, then re-rank by population boost.
@@ -88,25 +194,263 @@ impl Database {
             })
         })?;
 
-        // Collect and re-sort by boosted score (population boost affects ordering)
+        // Collect rows gathered so far - this is the FTS fetch boundary the
+        // time budget is measured against, so it's "the best results
+        // gathered so far" even if we bail before ranking finishes below.
         let mut results: Vec<GeocoderResult> = rows
             .filter_map(|r| r.ok())
             .map(|row| row.into_result())
             .collect();
 
-        // Sort by importance (descending) since population boost changes ranking
+        let mut degraded = false;
+        if let Some(deadline_ms) = query.deadline_ms {
+            degraded = start.elapsed().as_millis() as u64 >= deadline_ms;
+        }
+
+        if !degraded {
+            // Sort by importance (descending) since population boost changes ordering
+            results.sort_by(|a, b| {
+                b.importance
+                    .partial_cmp(&a.importance)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+
+            // Typo-tolerant fallback: only when FTS5 came up short and the
+            // caller opted in, so exact-only callers pay no cost.
+            if query.fuzzy && results.len() < query.limit {
+                let mut fuzzy_results =
+                    self.fuzzy_search(&query.text, query.limit, query.fuzzy_threshold)?;
+                // Skip divisions FTS5 already returned so the fallback only
+                // tops up with new hits.
+                fuzzy_results.retain(|f| !results.iter().any(|r| r.gers_id == f.gers_id));
+                fuzzy_results.truncate(query.limit - results.len());
+                results.append(&mut fuzzy_results);
+                results.sort_by(|a, b| {
+                    b.importance
+                        .partial_cmp(&a.importance)
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                });
+            }
+        }
+
+        // Truncate to requested limit
+        results.truncate(query.limit);
+
+        Ok(SearchResponse { results, degraded })
+    }
+
+    /// Score every division's `primary_name` against `text` with
+    /// Jaro-Winkler similarity, admitting those at or above `threshold`.
+    /// Used by `search` as a fallback when exact FTS5 matching comes up
+    /// short (e.g. "pittsburg" vs. "Pittsburgh").
+    fn fuzzy_search(
+        &self,
+        text: &str,
+        limit: usize,
+        threshold: f64,
+    ) -> Result<Vec<GeocoderResult>> {
+        let needle = text.to_lowercase();
+
+        let mut stmt = self.conn.prepare_cached(FUZZY_CANDIDATES_SQL)?;
+        let rows = stmt.query_map([], |row| {
+            let primary_name: String = row.get(3)?;
+            Ok((
+                primary_name.to_lowercase(),
+                DivisionRow {
+                    rowid: row.get(0)?,
+                    gers_id: row.get(1)?,
+                    division_type: row.get(2)?,
+                    primary_name,
+                    lat: row.get(4)?,
+                    lon: row.get(5)?,
+                    bbox_xmin: row.get(6)?,
+                    bbox_ymin: row.get(7)?,
+                    bbox_xmax: row.get(8)?,
+                    bbox_ymax: row.get(9)?,
+                    population: row.get(10)?,
+                    country: row.get(11)?,
+                    region: row.get(12)?,
+                    boosted_score: 0.0, // replaced below once similarity is known
+                },
+            ))
+        })?;
+
+        let mut results: Vec<GeocoderResult> = rows
+            .filter_map(|r| r.ok())
+            .filter_map(|(name, mut row)| {
+                let similarity = jaro_winkler(&needle, &name);
+                if similarity < threshold {
+                    return None;
+                }
+                row.boosted_score = calculate_fuzzy_boosted_score(similarity, row.population);
+                Some(row.into_result())
+            })
+            .collect();
+
         results.sort_by(|a, b| {
             b.importance
                 .partial_cmp(&a.importance)
                 .unwrap_or(std::cmp::Ordering::Equal)
         });
+        results.truncate(limit);
 
-        // Truncate to requested limit
-        results.truncate(query.limit);
+        Ok(results)
+    }
+
+    /// Set the candidate-count threshold above which `search_near` consults
+    /// an R-tree instead of ranking by distance directly. Lower this on
+    /// shards with very large result sets for a given query term.
+    pub fn with_proximity_rtree_threshold(mut self, threshold: usize) -> Self {
+        self.proximity_rtree_threshold = threshold;
+        self
+    }
+
+    /// Run `search`, then re-rank the candidates by great-circle distance
+    /// to `(lat, lon)`, blending distance into importance the way
+    /// [`LocationBias::Proximity`] does.
+    ///
+    /// For small candidate sets (the common case, since `search` truncates
+    /// to the query's `limit`) this sorts directly by the bias-adjusted importance.
+    /// Once the candidate count exceeds `proximity_rtree_threshold`, it
+    /// instead walks an R-tree's nearest-neighbor iterator to pull
+    /// candidates in distance order and blends the proximity bias into
+    /// `importance` without re-sorting, avoiding a full comparison sort on
+    /// the large candidate set (population-derived importance no longer
+    /// has a say over pure distance order in this regime, trading a bit of
+    /// ranking precision for speed). The tree is cached per-candidate-set
+    /// so repeated `search_near` calls against the same `search` results
+    /// (e.g. re-biasing toward a different anchor) don't rebuild it.
+    pub fn search_near(
+        &self,
+        query: &GeocoderQuery,
+        lat: f64,
+        lon: f64,
+    ) -> Result<Vec<GeocoderResult>> {
+        let candidates = self.search(query)?.results;
+        let bias = LocationBias::Proximity { lat, lon };
+
+        if candidates.len() <= self.proximity_rtree_threshold {
+            let mut results = candidates;
+            apply_location_bias(&mut results, &bias);
+            return Ok(results);
+        }
+
+        let fingerprint = fingerprint_candidates(&candidates);
+        let anchor = lat_lon_to_unit_xyz(lat, lon);
+
+        let mut cache = self.rtree_cache.borrow_mut();
+        let needs_rebuild = !matches!(cache.as_ref(), Some(entry) if entry.fingerprint == fingerprint);
+        if needs_rebuild {
+            let points = candidates
+                .iter()
+                .enumerate()
+                .map(|(index, result)| IndexedPoint {
+                    index,
+                    xyz: lat_lon_to_unit_xyz(result.lat, result.lon),
+                })
+                .collect();
+            *cache = Some(RTreeCacheEntry {
+                fingerprint,
+                tree: RTree::bulk_load(points),
+            });
+        }
+
+        let tree = &cache.as_ref().unwrap().tree;
+        let mut results: Vec<GeocoderResult> = tree
+            .nearest_neighbor_iter(&anchor)
+            .map(|point| candidates[point.index].clone())
+            .collect();
+        drop(cache);
+
+        // Blend the proximity bias into `importance` without re-sorting -
+        // the R-tree already produced distance order, and a full resort
+        // here would defeat the point of walking it instead of sorting.
+        blend_importance(&mut results, &bias);
+        Ok(results)
+    }
+
+    /// Reverse geocode a point, returning the divisions whose bounding box
+    /// contains it, smallest-area first (so the tightest admin unit - e.g.
+    /// city before region before country - comes first).
+    ///
+    /// If no division's bbox contains the point (e.g. open ocean near a
+    /// coastline), falls back to the single division whose centroid is
+    /// closest by great-circle distance rather than returning an empty list.
+    ///
+    /// `limit` caps the number of results; defaults to `DEFAULT_REVERSE_LIMIT`.
+    pub fn reverse(
+        &self,
+        lat: f64,
+        lon: f64,
+        limit: Option<usize>,
+    ) -> Result<Vec<ReverseResult>> {
+        let max_results = limit.unwrap_or(DEFAULT_REVERSE_LIMIT);
+
+        let mut stmt = self.conn.prepare_cached(REVERSE_GEOCODE_SQL)?;
+        // Bind the SQL's own LIMIT to max_results directly so a caller-supplied
+        // limit above DEFAULT_REVERSE_LIMIT isn't silently clipped by the query.
+        let rows = stmt.query_map(
+            rusqlite::params![lon, lat, max_results as i64],
+            Self::row_to_reverse_result,
+        )?;
+        let mut results: Vec<ReverseResult> = rows.filter_map(|r| r.ok()).collect();
 
+        if results.is_empty() {
+            if let Some(nearest) = self.reverse_nearest(lat, lon)? {
+                results.push(nearest);
+            }
+            return Ok(results);
+        }
+
+        results.truncate(max_results);
         Ok(results)
     }
 
+    /// Nearest-centroid fallback for [`Database::reverse`] when no bbox
+    /// contains the point. Scans all divisions, so it's only used for the
+    /// (rare) no-match case rather than the common path.
+    fn reverse_nearest(&self, lat: f64, lon: f64) -> Result<Option<ReverseResult>> {
+        let mut stmt = self.conn.prepare_cached(REVERSE_NEAREST_CANDIDATES_SQL)?;
+        let rows = stmt.query_map([], |row| {
+            let row_lat: f64 = row.get(3)?;
+            let row_lon: f64 = row.get(4)?;
+            Ok((
+                haversine_km(lat, lon, row_lat, row_lon),
+                ReverseResult {
+                    gers_id: row.get(0)?,
+                    subtype: row.get(1)?,
+                    primary_name: row.get(2)?,
+                    lat: row_lat,
+                    lon: row_lon,
+                    area: row.get(5)?,
+                    population: row.get(6)?,
+                    country: row.get(7)?,
+                    region: row.get(8)?,
+                },
+            ))
+        })?;
+
+        let nearest = rows
+            .filter_map(|r| r.ok())
+            .min_by(|(da, _), (db, _)| da.partial_cmp(db).unwrap_or(std::cmp::Ordering::Equal));
+
+        Ok(nearest.map(|(_, result)| result))
+    }
+
+    fn row_to_reverse_result(row: &rusqlite::Row<'_>) -> rusqlite::Result<ReverseResult> {
+        Ok(ReverseResult {
+            gers_id: row.get(0)?,
+            subtype: row.get(1)?,
+            primary_name: row.get(2)?,
+            lat: row.get(3)?,
+            lon: row.get(4)?,
+            area: row.get(9)?,
+            population: row.get(10)?,
+            country: row.get(11)?,
+            region: row.get(12)?,
+        })
+    }
+
     /// Get the number of records in the divisions table.
     pub fn count(&self) -> Result<u64> {
         let count: u64 = self
@@ -143,5 +487,255 @@ fn uuid_v4() -> String {
     format!("{:032x}", timestamp)
 }
 
-// Integration tests for Database are in crates/geocoder-core/tests/
+// Search/FTS integration tests for Database are in crates/geocoder-core/tests/
 // They require built shards: python scripts/build_shards.py --countries US
+//
+// reverse() doesn't depend on FTS5 or a real shard's contents, so it's
+// unit-tested directly below against a synthetic divisions_reverse table.
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIVISIONS_REVERSE_SCHEMA: &str = "
+        CREATE TABLE divisions_reverse (
+            gers_id TEXT,
+            subtype TEXT,
+            primary_name TEXT,
+            lat REAL,
+            lon REAL,
+            bbox_xmin REAL,
+            bbox_ymin REAL,
+            bbox_xmax REAL,
+            bbox_ymax REAL,
+            area REAL,
+            population INTEGER,
+            country TEXT,
+            region TEXT
+        );
+    ";
+
+    fn test_db(seed_sql: &str) -> Database {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(DIVISIONS_REVERSE_SCHEMA).unwrap();
+        conn.execute_batch(seed_sql).unwrap();
+
+        Database {
+            conn,
+            proximity_rtree_threshold: DEFAULT_PROXIMITY_RTREE_THRESHOLD,
+            rtree_cache: RefCell::new(None),
+        }
+    }
+
+    #[test]
+    fn reverse_orders_smallest_area_first() {
+        let db = test_db(
+            "INSERT INTO divisions_reverse
+                (gers_id, subtype, primary_name, lat, lon,
+                 bbox_xmin, bbox_ymin, bbox_xmax, bbox_ymax, area, population, country, region)
+             VALUES
+                ('city', 'locality', 'Springfield', 39.5, -89.6,
+                 -90, 39, -89, 40, 100.0, 50000, 'US', 'IL'),
+                ('country', 'country', 'United States', 39.5, -89.6,
+                 -125, 24, -66, 49, 1000000.0, 300000000, 'US', NULL);",
+        );
+
+        let results = db.reverse(39.5, -89.6, None).unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].gers_id, "city");
+        assert_eq!(results[1].gers_id, "country");
+    }
+
+    #[test]
+    fn reverse_truncates_to_the_requested_limit() {
+        let db = test_db(
+            "INSERT INTO divisions_reverse
+                (gers_id, subtype, primary_name, lat, lon,
+                 bbox_xmin, bbox_ymin, bbox_xmax, bbox_ymax, area, population, country, region)
+             VALUES
+                ('city', 'locality', 'Springfield', 39.5, -89.6,
+                 -90, 39, -89, 40, 100.0, 50000, 'US', 'IL'),
+                ('country', 'country', 'United States', 39.5, -89.6,
+                 -125, 24, -66, 49, 1000000.0, 300000000, 'US', NULL);",
+        );
+
+        let results = db.reverse(39.5, -89.6, Some(1)).unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].gers_id, "city");
+    }
+
+    #[test]
+    fn reverse_falls_back_to_nearest_centroid_when_no_bbox_contains_point() {
+        let db = test_db(
+            "INSERT INTO divisions_reverse
+                (gers_id, subtype, primary_name, lat, lon,
+                 bbox_xmin, bbox_ymin, bbox_xmax, bbox_ymax, area, population, country, region)
+             VALUES
+                ('near', 'locality', 'Near Place', 10.0, 10.0,
+                 9.0, 9.0, 11.0, 11.0, 100.0, 1000, 'XX', NULL),
+                ('far', 'locality', 'Far Place', 80.0, 80.0,
+                 79.0, 79.0, 81.0, 81.0, 100.0, 1000, 'YY', NULL);",
+        );
+
+        // Outside both bboxes, but much closer to 'near' than 'far'.
+        let results = db.reverse(20.0, 20.0, None).unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].gers_id, "near");
+    }
+
+    #[test]
+    fn reverse_returns_empty_when_no_divisions_exist() {
+        let db = test_db("");
+        let results = db.reverse(0.0, 0.0, None).unwrap();
+        assert!(results.is_empty());
+    }
+
+    const DIVISIONS_FTS_SCHEMA: &str = "
+        CREATE TABLE divisions (
+            gers_id TEXT,
+            type TEXT,
+            primary_name TEXT,
+            lat REAL,
+            lon REAL,
+            bbox_xmin REAL,
+            bbox_ymin REAL,
+            bbox_xmax REAL,
+            bbox_ymax REAL,
+            population INTEGER,
+            country TEXT,
+            region TEXT
+        );
+        CREATE VIRTUAL TABLE divisions_fts USING fts5(
+            primary_name, content='divisions', content_rowid='rowid'
+        );
+    ";
+
+    /// Build a `Database` whose `divisions`/`divisions_fts` tables hold one
+    /// "<name> Town" row per `(gers_id, lat, lon)`, all with equal
+    /// population so `search`'s boost/BM25 ranking doesn't interfere with
+    /// `search_near`'s distance-based reordering in the tests below.
+    fn test_db_with_towns(rows: &[(&str, f64, f64)]) -> Database {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(DIVISIONS_FTS_SCHEMA).unwrap();
+        for (gers_id, lat, lon) in rows {
+            conn.execute(
+                "INSERT INTO divisions
+                    (gers_id, type, primary_name, lat, lon,
+                     bbox_xmin, bbox_ymin, bbox_xmax, bbox_ymax, population, country, region)
+                 VALUES (?1, 'locality', ?1 || ' Town', ?2, ?3, ?3, ?2, ?3, ?2, 1000, 'US', 'IL')",
+                rusqlite::params![gers_id, lat, lon],
+            )
+            .unwrap();
+        }
+        conn.execute(
+            "INSERT INTO divisions_fts(rowid, primary_name) SELECT rowid, primary_name FROM divisions",
+            [],
+        )
+        .unwrap();
+
+        Database {
+            conn,
+            proximity_rtree_threshold: DEFAULT_PROXIMITY_RTREE_THRESHOLD,
+            rtree_cache: RefCell::new(None),
+        }
+    }
+
+    #[test]
+    fn search_near_sorts_small_candidate_sets_by_distance_directly() {
+        let db = test_db_with_towns(&[("far", 0.0, 10.0), ("near", 0.0, 1.0), ("mid", 0.0, 5.0)]);
+
+        let results = db.search_near(&GeocoderQuery::new("town"), 0.0, 0.0).unwrap();
+
+        assert_eq!(
+            results.iter().map(|r| r.gers_id.as_str()).collect::<Vec<_>>(),
+            vec!["near", "mid", "far"]
+        );
+    }
+
+    #[test]
+    fn search_near_rtree_path_produces_the_same_distance_order_as_the_direct_path() {
+        let db = test_db_with_towns(&[("far", 0.0, 10.0), ("near", 0.0, 1.0), ("mid", 0.0, 5.0)])
+            .with_proximity_rtree_threshold(2); // 3 candidates > 2 forces the R-tree path
+
+        let results = db.search_near(&GeocoderQuery::new("town"), 0.0, 0.0).unwrap();
+
+        assert_eq!(
+            results.iter().map(|r| r.gers_id.as_str()).collect::<Vec<_>>(),
+            vec!["near", "mid", "far"]
+        );
+    }
+
+    #[test]
+    fn search_near_reuses_the_cached_rtree_correctly_across_different_anchors() {
+        let db = test_db_with_towns(&[("west", 0.0, -5.0), ("east", 0.0, 5.0)])
+            .with_proximity_rtree_threshold(1); // 2 candidates > 1 forces the R-tree path
+
+        // First call builds and caches the R-tree against this candidate set.
+        let near_east = db.search_near(&GeocoderQuery::new("town"), 0.0, 5.0).unwrap();
+        assert_eq!(near_east[0].gers_id, "east");
+
+        // Same candidate set (same query), different anchor - the cached
+        // tree must still be walked nearest-neighbor-first from the new
+        // anchor rather than replaying the first call's order.
+        let near_west = db.search_near(&GeocoderQuery::new("town"), 0.0, -5.0).unwrap();
+        assert_eq!(near_west[0].gers_id, "west");
+    }
+
+    #[test]
+    fn search_respects_a_deadline_and_flags_the_response_as_degraded() {
+        let db = test_db_with_towns(&[("town", 0.0, 0.0)]);
+
+        let mut query = GeocoderQuery::new("town");
+        query.deadline_ms = Some(0);
+        let response = db.search(&query).unwrap();
+
+        // An already-elapsed deadline trips on the very first check.
+        assert!(response.degraded);
+    }
+
+    #[test]
+    fn an_elapsed_deadline_skips_the_fuzzy_fallback() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(DIVISIONS_FTS_SCHEMA).unwrap();
+        conn.execute(
+            "INSERT INTO divisions
+                (gers_id, type, primary_name, lat, lon,
+                 bbox_xmin, bbox_ymin, bbox_xmax, bbox_ymax, population, country, region)
+             VALUES ('exact', 'locality', 'Town', 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1000, 'US', 'IL'),
+                    ('other', 'locality', 'Springfield', 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1000, 'US', 'IL')",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO divisions_fts(rowid, primary_name) SELECT rowid, primary_name FROM divisions",
+            [],
+        )
+        .unwrap();
+        let db = Database {
+            conn,
+            proximity_rtree_threshold: DEFAULT_PROXIMITY_RTREE_THRESHOLD,
+            rtree_cache: RefCell::new(None),
+        };
+
+        let mut query = GeocoderQuery::new("town").with_limit(5);
+        query.fuzzy = true;
+        query.fuzzy_threshold = 0.0; // every division "matches" - isolates the degraded check
+
+        // Undegraded: FTS finds only "exact", so the fuzzy fallback tops up
+        // with "other" to fill the requested limit.
+        let warm = db.search(&query).unwrap();
+        assert!(!warm.degraded);
+        assert_eq!(warm.results.len(), 2);
+
+        // Degraded: the same query, but the deadline has already elapsed -
+        // `search` must return just the FTS hit and skip the fuzzy fallback
+        // (and the re-sort) entirely.
+        query.deadline_ms = Some(0);
+        let cold = db.search(&query).unwrap();
+        assert!(cold.degraded);
+        assert_eq!(cold.results.len(), 1);
+        assert_eq!(cold.results[0].gers_id, "exact");
+    }
+}
@@ -0,0 +1,14 @@
+//! Crate-wide error type.
+
+/// Errors produced by the geocoder core crate.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("sqlite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Convenience alias for results returned by this crate.
+pub type Result<T> = std::result::Result<T, Error>;
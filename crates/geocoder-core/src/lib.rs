@@ -5,11 +5,14 @@
 
 pub mod database;
 pub mod error;
+pub mod pager;
 pub mod query;
 pub mod types;
 
 pub use database::Database;
 pub use error::{Error, Result};
+pub use pager::PageReader;
 pub use types::{
-    DivisionRow, DivisionType, GeocoderQuery, GeocoderResult, LocationBias, ReverseResult,
+    BBox, DivisionRow, DivisionType, GeocoderQuery, GeocoderResult, LocationBias, ReverseResult,
+    SearchResponse,
 };
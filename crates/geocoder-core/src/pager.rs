@@ -0,0 +1,211 @@
+//! Lazy, range-read-backed SQLite access.
+//!
+//! `Database::from_pager` lets a caller open a shard without downloading it
+//! whole: SQLite's own b-tree traversal pulls only the pages a query
+//! actually touches, fetched on demand through a `PageReader` (e.g. R2
+//! range reads) registered as a custom VFS.
+
+use std::io;
+use std::time::Duration;
+
+use rusqlite::{Connection, OpenFlags};
+use sqlite_vfs::{DatabaseHandle, LockKind, OpenOptions, Vfs};
+
+use crate::error::{Error, Result};
+
+/// Size, in bytes, of the fixed SQLite file header.
+pub const SQLITE_HEADER_SIZE: u64 = 100;
+
+/// Offset of the big-endian `u16` page size field in the file header.
+const PAGE_SIZE_OFFSET: usize = 16;
+/// Offset of the big-endian `u32` page count field in the file header.
+const PAGE_COUNT_OFFSET: usize = 28;
+
+/// A read-only backend capable of range-reading a SQLite file by byte
+/// offset, e.g. an R2 object accessed via `bucket.get(key).range(...)`.
+///
+/// The header read (bytes `0..100`) must succeed before any other read -
+/// it's what `Database::from_pager` uses to learn `page_size`/`page_count`.
+/// Implementations should cache whatever they fetch under a versioned
+/// cache key (e.g. `{version}/{shard}#page{N}`); since shard paths are
+/// versioned and immutable, cached pages never need invalidation.
+///
+/// `Sync` is required because `sqlite_vfs::Vfs`/`DatabaseHandle` are -
+/// implementations needing interior mutability (e.g. a page cache) must
+/// use a `Sync` cell (`Mutex`, not `RefCell`).
+pub trait PageReader: Sync {
+    /// Read `len` bytes starting at `offset`.
+    fn read_range(&self, offset: u64, len: u64) -> io::Result<Vec<u8>>;
+
+    /// Total size of the file, if known ahead of time.
+    fn file_size(&self) -> io::Result<u64>;
+}
+
+/// SQLite file header fields relevant to page-level access.
+#[derive(Debug, Clone, Copy)]
+pub struct SqliteHeader {
+    pub page_size: u32,
+    pub page_count: u32,
+}
+
+impl SqliteHeader {
+    /// Parse a header from the first 100 bytes of a SQLite file.
+    pub fn parse(header: &[u8]) -> io::Result<Self> {
+        if header.len() < SQLITE_HEADER_SIZE as usize {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "short SQLite header read",
+            ));
+        }
+
+        // A page size of 1 in the header means 65536 (the field can't
+        // otherwise represent it in a u16).
+        let raw_page_size =
+            u16::from_be_bytes([header[PAGE_SIZE_OFFSET], header[PAGE_SIZE_OFFSET + 1]]);
+        let page_size = if raw_page_size == 1 {
+            65536
+        } else {
+            raw_page_size as u32
+        };
+
+        let page_count = u32::from_be_bytes([
+            header[PAGE_COUNT_OFFSET],
+            header[PAGE_COUNT_OFFSET + 1],
+            header[PAGE_COUNT_OFFSET + 2],
+            header[PAGE_COUNT_OFFSET + 3],
+        ]);
+
+        Ok(Self {
+            page_size,
+            page_count,
+        })
+    }
+
+    /// Byte range covering page `page_no` (1-indexed, per the SQLite format).
+    pub fn page_range(&self, page_no: u32) -> (u64, u64) {
+        let offset = (page_no.saturating_sub(1) as u64) * self.page_size as u64;
+        (offset, self.page_size as u64)
+    }
+}
+
+/// A read-only `DatabaseHandle` that proxies every read through a
+/// `PageReader` and refuses writes - shards are immutable snapshots, so
+/// there's no WAL/journal to support.
+pub(crate) struct PagedHandle<R: PageReader> {
+    pub(crate) reader: R,
+}
+
+impl<R: PageReader> DatabaseHandle for PagedHandle<R> {
+    type WalIndex = sqlite_vfs::WalDisabled;
+
+    fn size(&self) -> io::Result<u64> {
+        self.reader.file_size()
+    }
+
+    fn read_exact_at(&mut self, buf: &mut [u8], offset: u64) -> io::Result<()> {
+        let bytes = self.reader.read_range(offset, buf.len() as u64)?;
+        if bytes.len() != buf.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "short range read",
+            ));
+        }
+        buf.copy_from_slice(&bytes);
+        Ok(())
+    }
+
+    fn write_all_at(&mut self, _buf: &[u8], _offset: u64) -> io::Result<()> {
+        Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "shard pager is read-only",
+        ))
+    }
+
+    fn sync(&mut self, _data_only: bool) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn set_len(&mut self, _size: u64) -> io::Result<()> {
+        Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "shard pager is read-only",
+        ))
+    }
+
+    fn lock(&mut self, _lock: LockKind) -> io::Result<bool> {
+        Ok(true)
+    }
+
+    fn reserved(&mut self) -> io::Result<bool> {
+        Ok(false)
+    }
+
+    fn current_lock(&self) -> io::Result<LockKind> {
+        Ok(LockKind::Shared)
+    }
+
+    fn wal_index(&self, _readonly: bool) -> io::Result<Self::WalIndex> {
+        Ok(sqlite_vfs::WalDisabled::default())
+    }
+}
+
+/// Registers one `PagedHandle` per `Vfs`: a shard pager is single-database,
+/// so `open` just hands back a fresh handle over the same reader rather
+/// than resolving a filename.
+struct PagedVfs<R: PageReader + Clone> {
+    reader: R,
+}
+
+impl<R: PageReader + Clone + 'static> Vfs for PagedVfs<R> {
+    type Handle = PagedHandle<R>;
+
+    fn open(&self, _db: &str, _opts: OpenOptions) -> io::Result<Self::Handle> {
+        Ok(PagedHandle {
+            reader: self.reader.clone(),
+        })
+    }
+
+    fn delete(&self, _db: &str) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn exists(&self, _db: &str) -> io::Result<bool> {
+        Ok(true)
+    }
+
+    fn temporary_name(&self) -> String {
+        "paged-shard-tmp".to_string()
+    }
+
+    fn random(&self, buf: &mut [i8]) {
+        for byte in buf.iter_mut() {
+            *byte = 0;
+        }
+    }
+
+    fn sleep(&self, duration: Duration) -> Duration {
+        duration
+    }
+}
+
+/// Open a read-only SQLite connection backed entirely by `reader`, fetching
+/// only the header up front; every page SQLite's b-tree traversal touches
+/// after that is pulled on demand through `PageReader::read_range`.
+pub(crate) fn open_paged_connection<R: PageReader + Clone + 'static>(
+    vfs_name: &str,
+    reader: R,
+) -> Result<Connection> {
+    let header_bytes = reader.read_range(0, SQLITE_HEADER_SIZE)?;
+    SqliteHeader::parse(&header_bytes)?;
+
+    sqlite_vfs::register(vfs_name, PagedVfs { reader }, false)
+        .map_err(|e| Error::Io(io::Error::new(io::ErrorKind::Other, e.to_string())))?;
+
+    let conn = Connection::open_with_flags_and_vfs(
+        "shard.db",
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        vfs_name,
+    )?;
+
+    Ok(conn)
+}
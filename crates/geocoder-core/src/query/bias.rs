@@ -0,0 +1,166 @@
+//! Location-bias re-ranking: adjusts `GeocoderResult::importance` based on
+//! an anchor (country, viewport, ...) and re-sorts accordingly.
+
+use super::haversine_km;
+use super::merge::radius_km_from_population;
+use crate::types::{GeocoderResult, LocationBias};
+
+/// Importance multiplier applied to results matching the biased country.
+const COUNTRY_BIAS_MULTIPLIER: f64 = 1.5;
+
+/// Importance multiplier for results inside the small viewbox.
+const VIEWBOX_SMALL_MULTIPLIER: f64 = 1.0;
+/// Importance multiplier for results inside the large (but not small) viewbox.
+const VIEWBOX_LARGE_MULTIPLIER: f64 = 0.75;
+/// Importance multiplier for results outside both viewbox tiers.
+const VIEWBOX_OUTSIDE_MULTIPLIER: f64 = 0.5;
+
+/// Re-rank `results` in place according to `bias`, re-sorting by the
+/// adjusted importance afterward. [`LocationBias::None`] leaves importance
+/// unchanged, but the results are still re-sorted by it.
+pub fn apply_location_bias(results: &mut Vec<GeocoderResult>, bias: &LocationBias) {
+    blend_importance(results, bias);
+    resort_by_importance(results);
+}
+
+/// Adjust each result's `importance` in place according to `bias`, without
+/// re-sorting - for callers (e.g. `Database::search_near`'s R-tree path)
+/// that already have the results in the order they want to keep and would
+/// rather skip the `O(n log n)` resort [`apply_location_bias`] does.
+pub fn blend_importance(results: &mut [GeocoderResult], bias: &LocationBias) {
+    match bias {
+        LocationBias::None => {}
+        LocationBias::Country(code) => {
+            for result in results.iter_mut() {
+                if result.country.as_deref() == Some(code.as_str()) {
+                    result.importance *= COUNTRY_BIAS_MULTIPLIER;
+                }
+            }
+        }
+        LocationBias::Viewbox { small, large } => {
+            for result in results.iter_mut() {
+                let multiplier = if small.contains(result.lat, result.lon) {
+                    VIEWBOX_SMALL_MULTIPLIER
+                } else if large.contains(result.lat, result.lon) {
+                    VIEWBOX_LARGE_MULTIPLIER
+                } else {
+                    VIEWBOX_OUTSIDE_MULTIPLIER
+                };
+                result.importance *= multiplier;
+            }
+        }
+        LocationBias::Proximity { lat, lon } => {
+            for result in results.iter_mut() {
+                let distance_km = haversine_km(*lat, *lon, result.lat, result.lon);
+                // Scale the decay by the result's own population-derived
+                // catchment radius (see `merge::radius_km_from_population`)
+                // rather than a single fixed distance - a megacity stays
+                // relevant much farther out than a hamlet does.
+                let scale_km = radius_km_from_population(result.population);
+                result.importance *= 1.0 / (1.0 + distance_km / scale_km);
+            }
+        }
+    }
+}
+
+fn resort_by_importance(results: &mut [GeocoderResult]) {
+    results.sort_by(|a, b| {
+        b.importance
+            .partial_cmp(&a.importance)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{BBox, DivisionType};
+
+    fn result(lat: f64, lon: f64, country: &str, importance: f64) -> GeocoderResult {
+        GeocoderResult {
+            gers_id: format!("{lat},{lon}"),
+            division_type: DivisionType::Locality,
+            primary_name: "Test Place".to_string(),
+            lat,
+            lon,
+            bbox_xmin: lon,
+            bbox_ymin: lat,
+            bbox_xmax: lon,
+            bbox_ymax: lat,
+            population: None,
+            country: Some(country.to_string()),
+            region: None,
+            importance,
+        }
+    }
+
+    #[test]
+    fn country_bias_boosts_only_matching_country() {
+        let mut results = vec![result(0.0, 0.0, "US", 0.5), result(0.0, 0.0, "FR", 0.5)];
+        blend_importance(&mut results, &LocationBias::Country("US".to_string()));
+
+        assert_eq!(results[0].importance, 0.5 * COUNTRY_BIAS_MULTIPLIER);
+        assert_eq!(results[1].importance, 0.5);
+    }
+
+    #[test]
+    fn viewbox_bias_applies_tiered_multipliers() {
+        let small = BBox::new(-1.0, -1.0, 1.0, 1.0);
+        let large = BBox::new(-10.0, -10.0, 10.0, 10.0);
+        let bias = LocationBias::Viewbox { small, large };
+
+        let mut results = vec![
+            result(0.0, 0.0, "US", 1.0),   // inside small
+            result(5.0, 5.0, "US", 1.0),   // inside large only
+            result(50.0, 50.0, "US", 1.0), // outside both
+        ];
+        blend_importance(&mut results, &bias);
+
+        assert_eq!(results[0].importance, VIEWBOX_SMALL_MULTIPLIER);
+        assert_eq!(results[1].importance, VIEWBOX_LARGE_MULTIPLIER);
+        assert_eq!(results[2].importance, VIEWBOX_OUTSIDE_MULTIPLIER);
+    }
+
+    #[test]
+    fn proximity_bias_decays_with_distance() {
+        let mut results = vec![result(0.0, 0.0, "US", 1.0), result(10.0, 10.0, "US", 1.0)];
+        blend_importance(&mut results, &LocationBias::Proximity { lat: 0.0, lon: 0.0 });
+
+        assert_eq!(results[0].importance, 1.0); // zero distance from anchor
+        assert!(results[1].importance < 1.0);
+    }
+
+    #[test]
+    fn proximity_bias_decays_slower_for_higher_population_results() {
+        let mut village = result(10.0, 10.0, "US", 1.0);
+        village.population = Some(100);
+        let mut metropolis = result(10.0, 10.0, "US", 1.0);
+        metropolis.population = Some(10_000_000);
+
+        let bias = LocationBias::Proximity { lat: 0.0, lon: 0.0 };
+        let mut results = vec![village, metropolis];
+        blend_importance(&mut results, &bias);
+
+        // Same distance from the anchor, but the metropolis's wider
+        // population-derived catchment radius should decay less.
+        assert!(results[1].importance > results[0].importance);
+    }
+
+    #[test]
+    fn apply_location_bias_resorts_by_adjusted_importance() {
+        // FR starts ahead (0.5 > 0.4), but the US boost (0.4 * 1.5 = 0.6)
+        // should push US above FR after re-sorting.
+        let mut results = vec![result(0.0, 0.0, "FR", 0.5), result(0.0, 0.0, "US", 0.4)];
+        apply_location_bias(&mut results, &LocationBias::Country("US".to_string()));
+
+        assert_eq!(results[0].country.as_deref(), Some("US"));
+        assert_eq!(results[1].country.as_deref(), Some("FR"));
+    }
+
+    #[test]
+    fn blend_importance_is_a_noop_for_none_bias() {
+        let mut results = vec![result(0.0, 0.0, "US", 0.42)];
+        blend_importance(&mut results, &LocationBias::None);
+        assert_eq!(results[0].importance, 0.42);
+    }
+}
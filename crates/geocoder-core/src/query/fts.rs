@@ -0,0 +1,43 @@
+//! FTS5 MATCH query construction.
+
+/// Build an FTS5 `MATCH` expression from free-text user input.
+///
+/// Tokens are individually quoted (so punctuation inside a token can't be
+/// misread as FTS5 query syntax) and implicitly AND-ed together, matching
+/// FTS5's default column-query behavior. When `autocomplete` is set, the
+/// final token becomes a prefix query so a partially-typed last word still
+/// matches (e.g. `"new" "yor"*`).
+pub fn prepare_fts_query(text: &str, autocomplete: bool) -> String {
+    let tokens: Vec<String> = text
+        .split_whitespace()
+        .map(sanitize_token)
+        .filter(|token| !token.is_empty())
+        .collect();
+
+    if tokens.is_empty() {
+        return String::new();
+    }
+
+    let last_index = tokens.len() - 1;
+    tokens
+        .iter()
+        .enumerate()
+        .map(|(i, token)| {
+            if autocomplete && i == last_index {
+                format!("\"{}\"*", token)
+            } else {
+                format!("\"{}\"", token)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Strip characters FTS5 would otherwise interpret as query syntax
+/// (quotes, column filters, boolean operators) out of a raw token.
+fn sanitize_token(token: &str) -> String {
+    token
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect::<String>()
+}
@@ -0,0 +1,122 @@
+//! Jaro-Winkler string similarity, used as a typo-tolerant fallback when
+//! FTS5 MATCH returns no (or too few) hits.
+
+/// Characters of common prefix considered for the Winkler boost.
+const MAX_PREFIX_LEN: usize = 4;
+/// Weight applied per common-prefix character in the Winkler boost.
+const PREFIX_SCALING: f64 = 0.1;
+
+/// Jaro-Winkler similarity between two strings, in `[0.0, 1.0]`. Callers
+/// should normalize case (and whitespace) before comparing, since this
+/// treats differing case as a non-match character.
+pub fn jaro_winkler(a: &str, b: &str) -> f64 {
+    let jaro = jaro_similarity(a, b);
+    if jaro == 0.0 {
+        return 0.0;
+    }
+
+    let prefix_len = a
+        .chars()
+        .zip(b.chars())
+        .take(MAX_PREFIX_LEN)
+        .take_while(|(ca, cb)| ca == cb)
+        .count() as f64;
+
+    jaro + prefix_len * PREFIX_SCALING * (1.0 - jaro)
+}
+
+/// Jaro similarity: matching characters within a sliding window of
+/// `floor(max(|a|,|b|)/2) - 1`, adjusted for transpositions.
+fn jaro_similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let match_distance = a.len().max(b.len()) / 2;
+    let match_distance = match_distance.saturating_sub(1);
+
+    let mut a_matches = vec![false; a.len()];
+    let mut b_matches = vec![false; b.len()];
+    let mut matches = 0usize;
+
+    for (i, &ca) in a.iter().enumerate() {
+        let start = i.saturating_sub(match_distance);
+        let end = (i + match_distance + 1).min(b.len());
+        for (j, &cb) in b.iter().enumerate().take(end).skip(start) {
+            if b_matches[j] || cb != ca {
+                continue;
+            }
+            a_matches[i] = true;
+            b_matches[j] = true;
+            matches += 1;
+            break;
+        }
+    }
+
+    if matches == 0 {
+        return 0.0;
+    }
+
+    let mut transpositions = 0usize;
+    let mut k = 0;
+    for (i, &matched) in a_matches.iter().enumerate() {
+        if !matched {
+            continue;
+        }
+        while !b_matches[k] {
+            k += 1;
+        }
+        if a[i] != b[k] {
+            transpositions += 1;
+        }
+        k += 1;
+    }
+    let transpositions = transpositions / 2;
+
+    let matches = matches as f64;
+    (matches / a.len() as f64 + matches / b.len() as f64 + (matches - transpositions as f64) / matches) / 3.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_strings_score_one() {
+        assert_eq!(jaro_winkler("Springfield", "Springfield"), 1.0);
+    }
+
+    #[test]
+    fn empty_strings_score_one_empty_vs_nonempty_scores_zero() {
+        assert_eq!(jaro_winkler("", ""), 1.0);
+        assert_eq!(jaro_winkler("", "Springfield"), 0.0);
+    }
+
+    #[test]
+    fn classic_transposition_example() {
+        // The textbook Jaro-Winkler example (Winkler 1990).
+        let similarity = jaro_winkler("MARTHA", "MARHTA");
+        assert!(
+            (0.96..0.97).contains(&similarity),
+            "expected ~0.961, got {similarity}"
+        );
+    }
+
+    #[test]
+    fn common_prefix_boosts_similarity_over_plain_jaro() {
+        let with_shared_prefix = jaro_winkler("DWAYNE", "DUANE");
+        let without_shared_prefix = jaro_similarity("DWAYNE", "DUANE");
+        assert!(with_shared_prefix > without_shared_prefix);
+    }
+
+    #[test]
+    fn completely_different_strings_score_low() {
+        assert!(jaro_winkler("ABCDEF", "ZYXWVU") < 0.5);
+    }
+}
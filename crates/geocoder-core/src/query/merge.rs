@@ -0,0 +1,129 @@
+//! Population-derived duplicate merging, à la maps.me's
+//! `GetRadiusByPopulation`.
+
+use super::haversine_km;
+use crate::types::GeocoderResult;
+
+/// Scaling constant for `radius_km_from_population`: bigger cities claim a
+/// wider catchment (`radius_km ≈ k · population^(1/3)`).
+const RADIUS_SCALE_K: f64 = 0.75;
+/// Floor so unpopulated/unknown places still merge with an exact-duplicate
+/// sitting right on top of them.
+const MIN_RADIUS_KM: f64 = 1.0;
+/// Ceiling so a megacity's radius can't swallow a genuinely distinct,
+/// merely-same-named place a few hundred km away.
+const MAX_RADIUS_KM: f64 = 50.0;
+
+/// Effective catchment radius (km) for a division given its population.
+/// Used to decide whether two same-named hits are "the same place" for
+/// merge purposes - exposed publicly so proximity ranking can reuse it too.
+pub fn radius_km_from_population(population: Option<i64>) -> f64 {
+    let population = population.unwrap_or(0).max(0) as f64;
+    (RADIUS_SCALE_K * population.cbrt()).clamp(MIN_RADIUS_KM, MAX_RADIUS_KM)
+}
+
+/// Collapse near-identical hits - same primary name, centroids within the
+/// larger of their two population-derived radii - into the
+/// higher-population one. Keeps genuinely distinct, far-apart places (e.g.
+/// Springfield, MA vs. Springfield, IL) separate.
+///
+/// The higher-population hit of a merged pair survives; on equal population
+/// the one given first is kept, so callers should sort by importance first.
+pub fn merge_results(results: Vec<GeocoderResult>) -> Vec<GeocoderResult> {
+    let mut merged: Vec<GeocoderResult> = Vec::with_capacity(results.len());
+
+    'candidates: for candidate in results {
+        let candidate_radius = radius_km_from_population(candidate.population);
+
+        for existing in merged.iter_mut() {
+            if existing.primary_name != candidate.primary_name {
+                continue;
+            }
+
+            let radius = radius_km_from_population(existing.population).max(candidate_radius);
+            let distance = haversine_km(existing.lat, existing.lon, candidate.lat, candidate.lon);
+
+            if distance <= radius {
+                if candidate.population.unwrap_or(0) > existing.population.unwrap_or(0) {
+                    *existing = candidate;
+                }
+                continue 'candidates;
+            }
+        }
+
+        merged.push(candidate);
+    }
+
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::DivisionType;
+
+    fn result(name: &str, lat: f64, lon: f64, population: Option<i64>) -> GeocoderResult {
+        GeocoderResult {
+            gers_id: format!("{name}-{lat}-{lon}"),
+            division_type: DivisionType::Locality,
+            primary_name: name.to_string(),
+            lat,
+            lon,
+            bbox_xmin: lon,
+            bbox_ymin: lat,
+            bbox_xmax: lon,
+            bbox_ymax: lat,
+            population,
+            country: None,
+            region: None,
+            importance: 1.0,
+        }
+    }
+
+    #[test]
+    fn radius_grows_with_population_within_bounds() {
+        assert_eq!(radius_km_from_population(None), MIN_RADIUS_KM);
+        assert_eq!(radius_km_from_population(Some(0)), MIN_RADIUS_KM);
+        assert!(radius_km_from_population(Some(1_000)) > MIN_RADIUS_KM);
+        assert_eq!(radius_km_from_population(Some(i64::MAX)), MAX_RADIUS_KM);
+    }
+
+    #[test]
+    fn merges_same_named_places_within_population_radius() {
+        // Two "Springfield" hits ~0.5km apart - well within even the MIN_RADIUS_KM
+        // catchment, so they should collapse into the higher-population one.
+        let results = vec![
+            result("Springfield", 39.0, -89.0, Some(100)),
+            result("Springfield", 39.005, -89.0, Some(50_000)),
+        ];
+
+        let merged = merge_results(results);
+
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].population, Some(50_000));
+    }
+
+    #[test]
+    fn keeps_distant_same_named_places_separate() {
+        // Springfield, MA vs. Springfield, IL - same name, ~1450km apart,
+        // far outside any population-derived radius.
+        let results = vec![
+            result("Springfield", 42.1, -72.5, Some(150_000)),
+            result("Springfield", 39.8, -89.6, Some(116_000)),
+        ];
+
+        let merged = merge_results(results);
+
+        assert_eq!(merged.len(), 2);
+    }
+
+    #[test]
+    fn keeps_differently_named_places_separate_even_when_coincident() {
+        let results = vec![
+            result("Springfield", 39.0, -89.0, Some(100)),
+            result("Riverside", 39.0, -89.0, Some(100)),
+        ];
+
+        assert_eq!(merge_results(results).len(), 2);
+    }
+}
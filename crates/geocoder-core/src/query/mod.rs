@@ -2,10 +2,13 @@
 
 mod bias;
 mod fts;
+mod fuzzy;
 mod merge;
 
-pub use bias::apply_location_bias;
+pub use bias::{apply_location_bias, blend_importance};
 pub use fts::prepare_fts_query;
+pub use fuzzy::jaro_winkler;
+pub use merge::radius_km_from_population;
 pub use merge::merge_results;
 
 /// SQL query for searching divisions.
@@ -33,6 +36,29 @@ pub const SEARCH_DIVISIONS_SQL: &str = r#"
     LIMIT ?2
 "#;
 
+/// SQL query for the typo-tolerant fuzzy fallback: every division's name,
+/// scored in Rust via Jaro-Winkler rather than in SQL.
+///
+/// This is a full scan of `divisions`, so it's only run when FTS5 MATCH
+/// came up short and the caller opted into `GeocoderQuery::fuzzy`.
+pub const FUZZY_CANDIDATES_SQL: &str = r#"
+    SELECT
+        rowid,
+        gers_id,
+        type,
+        primary_name,
+        lat,
+        lon,
+        bbox_xmin,
+        bbox_ymin,
+        bbox_xmax,
+        bbox_ymax,
+        population,
+        country,
+        region
+    FROM divisions
+"#;
+
 /// Calculate boosted score from BM25 and population.
 /// Lower score = better match.
 pub fn calculate_boosted_score(bm25_score: f64, population: Option<i64>) -> f64 {
@@ -42,6 +68,20 @@ pub fn calculate_boosted_score(bm25_score: f64, population: Option<i64>) -> f64
     }
 }
 
+/// Pseudo-BM25 base for fuzzy (non-FTS) matches, scaled so that even a
+/// near-perfect similarity still scores worse than a genuine BM25 hit
+/// (which is negative) - this keeps fuzzy matches ranked just below exact
+/// ones rather than mixed in among them.
+const FUZZY_SCORE_SCALE: f64 = 10.0;
+
+/// Fold a Jaro-Winkler similarity into a boosted score using the same
+/// population boost exact matches get, so a popular near-miss can still
+/// outrank an obscure one.
+pub fn calculate_fuzzy_boosted_score(similarity: f64, population: Option<i64>) -> f64 {
+    let pseudo_bm25 = (1.0 - similarity) * FUZZY_SCORE_SCALE;
+    calculate_boosted_score(pseudo_bm25, population)
+}
+
 /// SQL query for reverse geocoding (bbox containment).
 pub const REVERSE_GEOCODE_SQL: &str = r#"
     SELECT
@@ -64,5 +104,36 @@ pub const REVERSE_GEOCODE_SQL: &str = r#"
       AND bbox_ymin <= ?2
       AND bbox_ymax >= ?2
     ORDER BY area ASC
-    LIMIT 50
+    LIMIT ?3
 "#;
+
+/// SQL query for the nearest-centroid fallback used when no bbox contains
+/// the queried point.
+pub const REVERSE_NEAREST_CANDIDATES_SQL: &str = r#"
+    SELECT
+        gers_id,
+        subtype,
+        primary_name,
+        lat,
+        lon,
+        area,
+        population,
+        country,
+        region
+    FROM divisions_reverse
+"#;
+
+/// Mean Earth radius in kilometers, matching the value used by most
+/// haversine implementations (e.g. Nominatim, maps.me).
+pub const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// Great-circle distance between two lat/lon points, in kilometers.
+pub fn haversine_km(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let (phi1, phi2) = (lat1.to_radians(), lat2.to_radians());
+    let d_phi = (lat2 - lat1).to_radians();
+    let d_lambda = (lon2 - lon1).to_radians();
+
+    let a = (d_phi / 2.0).sin().powi(2) + phi1.cos() * phi2.cos() * (d_lambda / 2.0).sin().powi(2);
+
+    2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+}
@@ -0,0 +1,231 @@
+//! Shared data types for queries and results.
+
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ValueRef};
+
+/// Overture Maps division subtype.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DivisionType {
+    Country,
+    Dependency,
+    Region,
+    County,
+    LocalAdmin,
+    Locality,
+    Borough,
+    Neighborhood,
+}
+
+impl FromSql for DivisionType {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value.as_str()? {
+            "country" => Ok(DivisionType::Country),
+            "dependency" => Ok(DivisionType::Dependency),
+            "region" => Ok(DivisionType::Region),
+            "county" => Ok(DivisionType::County),
+            "localadmin" => Ok(DivisionType::LocalAdmin),
+            "locality" => Ok(DivisionType::Locality),
+            "borough" => Ok(DivisionType::Borough),
+            "neighborhood" => Ok(DivisionType::Neighborhood),
+            _ => Err(FromSqlError::InvalidType),
+        }
+    }
+}
+
+/// A single row fetched from `divisions`/`divisions_fts`, before conversion
+/// into a public [`GeocoderResult`].
+pub struct DivisionRow {
+    pub rowid: i64,
+    pub gers_id: String,
+    pub division_type: DivisionType,
+    pub primary_name: String,
+    pub lat: f64,
+    pub lon: f64,
+    pub bbox_xmin: f64,
+    pub bbox_ymin: f64,
+    pub bbox_xmax: f64,
+    pub bbox_ymax: f64,
+    pub population: Option<i64>,
+    pub country: Option<String>,
+    pub region: Option<String>,
+    pub boosted_score: f64,
+}
+
+impl DivisionRow {
+    /// Convert the internal row into the public result type, squashing the
+    /// boosted BM25 score into a 0..1 importance via a logistic curve.
+    pub fn into_result(self) -> GeocoderResult {
+        GeocoderResult {
+            gers_id: self.gers_id,
+            division_type: self.division_type,
+            primary_name: self.primary_name,
+            lat: self.lat,
+            lon: self.lon,
+            bbox_xmin: self.bbox_xmin,
+            bbox_ymin: self.bbox_ymin,
+            bbox_xmax: self.bbox_xmax,
+            bbox_ymax: self.bbox_ymax,
+            population: self.population,
+            country: self.country,
+            region: self.region,
+            importance: 1.0 / (1.0 + self.boosted_score.exp()),
+        }
+    }
+}
+
+/// A geocoding result returned to callers.
+#[derive(Debug, Clone)]
+pub struct GeocoderResult {
+    pub gers_id: String,
+    pub division_type: DivisionType,
+    pub primary_name: String,
+    pub lat: f64,
+    pub lon: f64,
+    pub bbox_xmin: f64,
+    pub bbox_ymin: f64,
+    pub bbox_xmax: f64,
+    pub bbox_ymax: f64,
+    pub population: Option<i64>,
+    pub country: Option<String>,
+    pub region: Option<String>,
+    /// Normalized 0..1 ranking score (higher is better).
+    pub importance: f64,
+}
+
+/// Response wrapper for [`crate::Database::search`].
+#[derive(Debug, Clone)]
+pub struct SearchResponse {
+    pub results: Vec<GeocoderResult>,
+    /// True if `deadline_ms` elapsed before ranking finished, meaning
+    /// `results` is in raw BM25 order rather than sorted by the
+    /// population-boosted importance, and the fuzzy fallback (if requested)
+    /// was skipped - so the set may also be incomplete.
+    pub degraded: bool,
+}
+
+/// A reverse-geocoding result: the division whose bounding box contains (or
+/// is nearest to) the queried point.
+#[derive(Debug, Clone)]
+pub struct ReverseResult {
+    pub gers_id: String,
+    pub subtype: DivisionType,
+    pub primary_name: String,
+    pub lat: f64,
+    pub lon: f64,
+    pub area: f64,
+    pub population: Option<i64>,
+    pub country: Option<String>,
+    pub region: Option<String>,
+}
+
+/// An axis-aligned bounding box in WGS84 lon/lat degrees.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BBox {
+    pub xmin: f64,
+    pub ymin: f64,
+    pub xmax: f64,
+    pub ymax: f64,
+}
+
+impl BBox {
+    pub fn new(xmin: f64, ymin: f64, xmax: f64, ymax: f64) -> Self {
+        Self {
+            xmin,
+            ymin,
+            xmax,
+            ymax,
+        }
+    }
+
+    /// Whether the given point falls within this box.
+    pub fn contains(&self, lat: f64, lon: f64) -> bool {
+        lon >= self.xmin && lon <= self.xmax && lat >= self.ymin && lat <= self.ymax
+    }
+
+    /// Expand this box outward from its center by `factor` (e.g. `2.0`
+    /// doubles both the width and height).
+    pub fn expand(&self, factor: f64) -> BBox {
+        let width = self.xmax - self.xmin;
+        let height = self.ymax - self.ymin;
+        let cx = self.xmin + width / 2.0;
+        let cy = self.ymin + height / 2.0;
+
+        BBox {
+            xmin: cx - (width * factor) / 2.0,
+            ymin: cy - (height * factor) / 2.0,
+            xmax: cx + (width * factor) / 2.0,
+            ymax: cy + (height * factor) / 2.0,
+        }
+    }
+}
+
+/// How to bias/re-rank search results toward a geographic area.
+#[derive(Debug, Clone, Default)]
+pub enum LocationBias {
+    #[default]
+    None,
+    /// Elevate results within (or matching) the given country code.
+    Country(String),
+    /// Elevate results within a viewport. `small` is the visible map
+    /// viewport; `large` is a wider catchment area around it.
+    Viewbox { small: BBox, large: BBox },
+    /// Re-rank results by great-circle distance to an anchor point.
+    Proximity { lat: f64, lon: f64 },
+}
+
+impl LocationBias {
+    /// The factor by which `BBox::expand` widens `small` to derive `large`
+    /// for [`LocationBias::viewbox`].
+    pub const DEFAULT_VIEWBOX_EXPANSION: f64 = 3.0;
+
+    /// Build a `Viewbox` bias from a single small box, deriving the larger
+    /// catchment box by expanding it.
+    pub fn viewbox(small: BBox) -> Self {
+        let large = small.expand(Self::DEFAULT_VIEWBOX_EXPANSION);
+        LocationBias::Viewbox { small, large }
+    }
+}
+
+/// Default Jaro-Winkler similarity a candidate must clear to be admitted
+/// by the fuzzy fallback.
+pub const DEFAULT_FUZZY_THRESHOLD: f64 = 0.9;
+
+/// A search query against the divisions index.
+#[derive(Debug, Clone)]
+pub struct GeocoderQuery {
+    pub text: String,
+    pub limit: usize,
+    pub autocomplete: bool,
+    pub bias: LocationBias,
+    /// When true and FTS5 returns fewer than `limit` results, fall back to
+    /// scoring candidate names with Jaro-Winkler similarity. Exact-only
+    /// callers leave this `false` to avoid the extra scan.
+    pub fuzzy: bool,
+    /// Minimum Jaro-Winkler similarity for a fuzzy candidate to be admitted.
+    pub fuzzy_threshold: f64,
+    /// Optional time budget for the search. If set and the budget elapses
+    /// before ranking finishes, `search` returns early with whatever it has
+    /// gathered so far and flags the response as `degraded`. Useful for
+    /// autocomplete endpoints that must respond within a fixed latency.
+    pub deadline_ms: Option<u64>,
+}
+
+impl GeocoderQuery {
+    /// Create a new query with the default limit and no bias.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            limit: 10,
+            autocomplete: false,
+            bias: LocationBias::None,
+            fuzzy: false,
+            fuzzy_threshold: DEFAULT_FUZZY_THRESHOLD,
+            deadline_ms: None,
+        }
+    }
+
+    /// Set the maximum number of results to return.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+}
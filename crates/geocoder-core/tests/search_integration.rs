@@ -34,7 +34,7 @@ fn test_search_new_york() {
     };
 
     let query = GeocoderQuery::new("new york");
-    let results = db.search(&query).unwrap();
+    let results = db.search(&query).unwrap().results;
 
     assert!(!results.is_empty(), "Should return results for 'new york'");
 
@@ -66,7 +66,7 @@ fn test_search_short_name_nyc() {
     };
 
     let query = GeocoderQuery::new("nyc");
-    let results = db.search(&query).unwrap();
+    let results = db.search(&query).unwrap().results;
 
     assert!(!results.is_empty(), "Should return results for 'nyc'");
     assert!(
@@ -83,7 +83,7 @@ fn test_search_alternate_name_big_apple() {
     };
 
     let query = GeocoderQuery::new("big apple");
-    let results = db.search(&query).unwrap();
+    let results = db.search(&query).unwrap().results;
 
     assert!(!results.is_empty(), "Should return results for 'big apple'");
     assert!(
@@ -100,7 +100,7 @@ fn test_search_boston() {
     };
 
     let query = GeocoderQuery::new("boston");
-    let results = db.search(&query).unwrap();
+    let results = db.search(&query).unwrap().results;
 
     assert!(!results.is_empty(), "Should return results for 'boston'");
 
@@ -126,7 +126,7 @@ fn test_autocomplete() {
 
     let mut query = GeocoderQuery::new("bost");
     query.autocomplete = true;
-    let results = db.search(&query).unwrap();
+    let results = db.search(&query).unwrap().results;
 
     assert!(
         !results.is_empty(),
@@ -147,7 +147,7 @@ fn test_location_bias_returns_many_results() {
 
     // Request limit=5, but search should return more for bias to work with
     let query = GeocoderQuery::new("paris").with_limit(5);
-    let results = db.search(&query).unwrap();
+    let results = db.search(&query).unwrap().results;
 
     // Database::search should return more than 5 results (up to 10x limit)
     // This allows location bias to elevate results that wouldn't make the initial cut
@@ -168,7 +168,7 @@ fn test_location_bias_us_elevates_us_results() {
 
     // Search for a common name that exists in multiple countries
     let query = GeocoderQuery::new("springfield").with_limit(10);
-    let mut results = db.search(&query).unwrap();
+    let mut results = db.search(&query).unwrap().results;
 
     // Apply US country bias
     apply_location_bias(&mut results, &LocationBias::Country("US".to_string()));
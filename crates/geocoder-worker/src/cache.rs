@@ -0,0 +1,152 @@
+//! Swappable shard cache backend.
+//!
+//! `ShardLoader` talks to this trait instead of the Cloudflare Cache API
+//! directly, so the shard-loading and search/reverse-geocode paths can be
+//! exercised in plain `cargo test` against synthetic shards, off-platform.
+
+use async_trait::async_trait;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use worker::*;
+
+/// A cache for shard/metadata bytes keyed by a logical path (e.g.
+/// `{version}/shards/US.db`).
+#[async_trait(?Send)]
+pub trait ShardCache {
+    /// Fetch cached bytes for `key`, if present.
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>>;
+    /// Store `bytes` under `key` for approximately `ttl` seconds.
+    async fn put(&self, key: &str, bytes: Vec<u8>, ttl: u64) -> Result<()>;
+}
+
+/// Cache key prefix (uses custom domain for Cache API to work).
+const CACHE_PREFIX: &str = "https://geocoder.bradr.dev/__cache/";
+
+/// Production backend: Cloudflare's edge Cache API.
+pub struct CloudflareCache {
+    cache: Cache,
+}
+
+impl CloudflareCache {
+    pub fn new() -> Self {
+        Self {
+            cache: Cache::default(),
+        }
+    }
+}
+
+impl Default for CloudflareCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait(?Send)]
+impl ShardCache for CloudflareCache {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let cache_key = format!("{}{}", CACHE_PREFIX, key);
+        let request = Request::new(&cache_key, Method::Get)?;
+
+        if let Some(mut response) = self.cache.get(&request, false).await? {
+            console_log!("Cache HIT: {}", key);
+            return Ok(Some(response.bytes().await?));
+        }
+
+        console_log!("Cache MISS: {}", key);
+        Ok(None)
+    }
+
+    async fn put(&self, key: &str, bytes: Vec<u8>, ttl: u64) -> Result<()> {
+        let cache_key = format!("{}{}", CACHE_PREFIX, key);
+
+        let headers = Headers::new();
+        headers.set("Cache-Control", &format!("s-maxage={}", ttl))?;
+        headers.set("Content-Type", "application/octet-stream")?;
+
+        let cache_response = Response::from_bytes(bytes)?.with_headers(headers);
+        let cache_request = Request::new(&cache_key, Method::Get)?;
+
+        // Best effort, don't fail the request if caching fails.
+        if let Err(e) = self.cache.put(&cache_request, cache_response).await {
+            console_log!("Cache PUT failed for {}: {:?}", key, e);
+        }
+
+        Ok(())
+    }
+}
+
+/// Test/off-platform backend: a plain in-memory map, ignoring TTLs. Lets
+/// the shard-loading and search/reverse-geocode paths run under
+/// `cargo test` against synthetic shards without a Workers runtime.
+#[derive(Default)]
+pub struct DummyCache {
+    entries: RefCell<HashMap<String, Vec<u8>>>,
+}
+
+impl DummyCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait(?Send)]
+impl ShardCache for DummyCache {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        Ok(self.entries.borrow().get(key).cloned())
+    }
+
+    async fn put(&self, key: &str, bytes: Vec<u8>, _ttl: u64) -> Result<()> {
+        self.entries.borrow_mut().insert(key.to_string(), bytes);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `DummyCache::get`/`put` never actually suspend, so a real async
+    /// executor (unavailable off the Workers runtime in plain `cargo test`)
+    /// isn't needed - just poll once with a waker that's never used.
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        use std::task::{Context, Poll, Wake, Waker};
+
+        struct NoopWake;
+        impl Wake for NoopWake {
+            fn wake(self: std::sync::Arc<Self>) {}
+        }
+
+        let waker = Waker::from(std::sync::Arc::new(NoopWake));
+        let mut cx = Context::from_waker(&waker);
+        let mut future = Box::pin(future);
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(value) => value,
+            Poll::Pending => panic!("DummyCache future unexpectedly suspended"),
+        }
+    }
+
+    #[test]
+    fn miss_then_put_then_hit() {
+        let cache = DummyCache::new();
+
+        assert!(block_on(cache.get("US/shards/US.db")).unwrap().is_none());
+
+        block_on(cache.put("US/shards/US.db", vec![1, 2, 3], 3600)).unwrap();
+
+        assert_eq!(
+            block_on(cache.get("US/shards/US.db")).unwrap(),
+            Some(vec![1, 2, 3])
+        );
+    }
+
+    #[test]
+    fn entries_are_keyed_independently() {
+        let cache = DummyCache::new();
+        block_on(cache.put("a", vec![1], 3600)).unwrap();
+        block_on(cache.put("b", vec![2], 3600)).unwrap();
+
+        assert_eq!(block_on(cache.get("a")).unwrap(), Some(vec![1]));
+        assert_eq!(block_on(cache.get("b")).unwrap(), Some(vec![2]));
+        assert!(block_on(cache.get("c")).unwrap().is_none());
+    }
+}
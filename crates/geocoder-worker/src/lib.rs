@@ -0,0 +1,11 @@
+//! Cloudflare Workers edge service for Overture geocoding.
+
+pub mod bucket;
+pub mod cache;
+pub mod pager;
+pub mod stac;
+
+pub use bucket::{InMemoryBucket, R2ShardBucket, ShardBucket};
+pub use cache::{CloudflareCache, DummyCache, ShardCache};
+pub use pager::R2PageReader;
+pub use stac::ShardLoader;
@@ -0,0 +1,99 @@
+//! R2-backed `PageReader` for on-demand SQLite page access.
+//!
+//! Lets `ShardLoader` open a shard via `Database::from_pager` instead of
+//! downloading it whole, for the common case of a point lookup that only
+//! touches a handful of b-tree pages.
+
+use std::collections::HashMap;
+use std::io;
+use std::sync::{Arc, Mutex};
+
+use geocoder_core::PageReader;
+
+use crate::bucket::ShardBucket;
+
+/// Upper bound on page-fault round-trips a single paged query may take
+/// before giving up and letting the caller fall back to a whole-shard
+/// download - covers even a pathologically deep b-tree without turning a
+/// missing shard into an unbounded request loop.
+pub const MAX_PAGE_FAULTS: u32 = 64;
+
+/// Range-reads pages of a single R2 object on demand, caching each
+/// page-range by offset under a versioned object key - since shard paths
+/// are versioned and immutable, cached pages never need invalidation.
+///
+/// Pages must be fetched ahead of time with [`R2PageReader::warm`];
+/// `read_range` only ever serves from the cache, since SQLite's VFS calls
+/// are synchronous and R2 reads are not. A miss records the offset/length
+/// SQLite asked for in [`R2PageReader::take_page_fault`] instead of just
+/// erroring blind, so the caller can warm exactly that page and retry -
+/// this is what makes paging "on demand" rather than a prefix guess.
+///
+/// Uses `Arc<Mutex<..>>` rather than `Rc<RefCell<..>>` because
+/// `geocoder_core::PageReader` requires `Sync` (the `sqlite-vfs` traits it
+/// backs are `Sync`); the Workers runtime is single-threaded, so the
+/// locking here never contends.
+#[derive(Clone)]
+pub struct R2PageReader {
+    key: String,
+    file_size: u64,
+    pages: Arc<Mutex<HashMap<u64, Vec<u8>>>>,
+    page_fault: Arc<Mutex<Option<(u64, u64)>>>,
+}
+
+impl R2PageReader {
+    pub fn new(key: String, file_size: u64) -> Self {
+        Self {
+            key,
+            file_size,
+            pages: Arc::new(Mutex::new(HashMap::new())),
+            page_fault: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Fetch and cache the byte range `[offset, offset + length)` through `bucket`.
+    pub async fn warm(
+        &self,
+        bucket: &dyn ShardBucket,
+        offset: u64,
+        length: u64,
+    ) -> worker::Result<()> {
+        let bytes = bucket
+            .get_range(&self.key, offset, length)
+            .await?
+            .ok_or_else(|| worker::Error::RustError(format!("{} not found", self.key)))?;
+
+        self.pages.lock().unwrap().insert(offset, bytes);
+        Ok(())
+    }
+
+    /// The `(offset, length)` of the most recent unwarmed read SQLite's
+    /// traversal asked for, if any, consuming it - lets the caller warm
+    /// precisely the page the b-tree walk is actually blocked on and retry,
+    /// rather than guessing a prefix of pages to pre-fetch.
+    pub fn take_page_fault(&self) -> Option<(u64, u64)> {
+        self.page_fault.lock().unwrap().take()
+    }
+}
+
+impl PageReader for R2PageReader {
+    fn read_range(&self, offset: u64, len: u64) -> io::Result<Vec<u8>> {
+        self.pages
+            .lock()
+            .unwrap()
+            .get(&offset)
+            .filter(|bytes| bytes.len() as u64 >= len)
+            .map(|bytes| bytes[..len as usize].to_vec())
+            .ok_or_else(|| {
+                *self.page_fault.lock().unwrap() = Some((offset, len));
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("page at offset {} in {} was not warmed", offset, self.key),
+                )
+            })
+    }
+
+    fn file_size(&self) -> io::Result<u64> {
+        Ok(self.file_size)
+    }
+}
@@ -1,6 +1,17 @@
 //! STAC catalog loading and shard management with edge caching.
 
-use geocoder_core::{query::apply_location_bias, Database, GeocoderQuery, GeocoderResult, LocationBias, ReverseResult};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use crate::bucket::{R2ShardBucket, ShardBucket};
+use crate::cache::{CloudflareCache, ShardCache};
+use crate::pager::{R2PageReader, MAX_PAGE_FAULTS};
+use geocoder_core::{
+    pager::{SqliteHeader, SQLITE_HEADER_SIZE},
+    query::{apply_location_bias, merge_results},
+    Database, GeocoderQuery, GeocoderResult, LocationBias, ReverseResult,
+};
 use serde::Deserialize;
 use worker::*;
 
@@ -9,14 +20,161 @@ const CATALOG_CACHE_TTL: u64 = 300;    // 5 minutes - need fresh version pointer
 const COLLECTION_CACHE_TTL: u64 = 300; // 5 minutes - contains shard list
 const SHARD_CACHE_TTL: u64 = 3600;     // 1 hour - versioned paths = natural invalidation
 
-// Cache key prefix (uses custom domain for Cache API to work)
-const CACHE_PREFIX: &str = "https://geocoder.bradr.dev/__cache/";
+/// Env var naming the per-request shard fetch budget, in bytes. Falls back
+/// to [`DEFAULT_SHARD_BYTE_BUDGET`] when unset or unparsable.
+const SHARD_BYTE_BUDGET_VAR: &str = "SHARD_BYTE_BUDGET";
+
+/// Default cap on cumulative shard bytes a single `search`/`reverse_geocode`
+/// call may pull from R2 - enough for a HEAD shard plus one country shard
+/// for most countries, without risking an OOM on the isolate from a
+/// pathological fan-out.
+const DEFAULT_SHARD_BYTE_BUDGET: u64 = 64 * 1024 * 1024;
+
+/// Env vars sizing the isolate-lifetime `Database` LRU (see
+/// [`DatabaseLru`]). Fall back to [`DEFAULT_DB_CACHE_MAX_ENTRIES`]/
+/// [`DEFAULT_DB_CACHE_MAX_BYTES`] when unset or unparsable.
+const DB_CACHE_MAX_ENTRIES_VAR: &str = "DB_CACHE_MAX_ENTRIES";
+const DB_CACHE_MAX_BYTES_VAR: &str = "DB_CACHE_MAX_BYTES";
+
+/// Default number of opened shard `Database` handles kept warm per isolate
+/// - a HEAD shard plus a handful of recently-queried country shards.
+const DEFAULT_DB_CACHE_MAX_ENTRIES: usize = 8;
+
+/// Default cap on the combined source-byte size of cached `Database`
+/// handles per isolate, to bound memory alongside `DB_CACHE_MAX_ENTRIES`.
+const DEFAULT_DB_CACHE_MAX_BYTES: u64 = 128 * 1024 * 1024;
+
+/// Hit/miss and egress counters for a single `search`/`reverse_geocode`
+/// call - lets operators tune `SHARD_CACHE_TTL`/`COLLECTION_CACHE_TTL`
+/// from real numbers instead of guessing. Read back via
+/// [`ShardLoader::cache_stats`] after the call, e.g. to bind onto
+/// Cloudflare Analytics Engine.
+#[derive(Debug, Clone, Default)]
+pub struct CacheStats {
+    pub cache_hits: u64,
+    pub cache_misses: u64,
+    pub bytes_from_cache: u64,
+    pub bytes_from_r2: u64,
+    pub shards_opened: u64,
+    /// Hits served from the isolate-lifetime `Database` LRU, skipping both
+    /// the Cache API and `Database::from_bytes` entirely.
+    pub db_cache_hits: u64,
+    /// Number of shard queries issued per shard ID (e.g. "HEAD", "US").
+    pub shard_queries: std::collections::HashMap<String, u64>,
+}
+
+impl CacheStats {
+    /// Total bytes loaded (cache + R2), for fetch-budget accounting.
+    fn bytes_loaded(&self) -> u64 {
+        self.bytes_from_cache + self.bytes_from_r2
+    }
+
+    fn record_shard_query(&mut self, shard_id: &str) {
+        *self.shard_queries.entry(shard_id.to_string()).or_insert(0) += 1;
+    }
+}
+
+struct DatabaseLruEntry {
+    db: Rc<Database>,
+    bytes: u64,
+}
+
+/// Bounded LRU of already-opened shard `Database` handles, shared across
+/// every `ShardLoader` in the isolate via [`DB_CACHE`]. A warm isolate
+/// handling a burst of requests against the same shard(s) skips both the
+/// Cache API round-trip and re-parsing the SQLite header through
+/// `Database::from_bytes` for every hit.
+///
+/// Only whole-shard (`Database::from_bytes`) opens are cached - a paged
+/// `Database::from_pager` only has a handful of pages warmed for the
+/// lookup that opened it, so caching it would make unrelated lookups fail
+/// instead of just costing a fetch.
+struct DatabaseLru {
+    entries: RefCell<std::collections::HashMap<String, DatabaseLruEntry>>,
+    /// Least-recently-used key at the front, most-recently-used at the back.
+    order: RefCell<VecDeque<String>>,
+    max_entries: std::cell::Cell<usize>,
+    max_bytes: std::cell::Cell<u64>,
+}
+
+impl DatabaseLru {
+    fn new(max_entries: usize, max_bytes: u64) -> Self {
+        Self {
+            entries: RefCell::new(std::collections::HashMap::new()),
+            order: RefCell::new(VecDeque::new()),
+            max_entries: std::cell::Cell::new(max_entries),
+            max_bytes: std::cell::Cell::new(max_bytes),
+        }
+    }
+
+    /// Re-apply configured limits, evicting if the isolate's env vars
+    /// shrank them since the cache was first populated.
+    fn configure(&self, max_entries: usize, max_bytes: u64) {
+        self.max_entries.set(max_entries);
+        self.max_bytes.set(max_bytes);
+        self.evict();
+    }
+
+    fn get(&self, key: &str) -> Option<Rc<Database>> {
+        let hit = self.entries.borrow().get(key).map(|e| e.db.clone());
+        if hit.is_some() {
+            self.touch(key);
+        }
+        hit
+    }
+
+    fn insert(&self, key: String, db: Database, bytes: u64) -> Rc<Database> {
+        let db = Rc::new(db);
+        self.entries
+            .borrow_mut()
+            .insert(key.clone(), DatabaseLruEntry { db: db.clone(), bytes });
+        self.touch(&key);
+        self.evict();
+        db
+    }
+
+    fn touch(&self, key: &str) {
+        let mut order = self.order.borrow_mut();
+        order.retain(|k| k != key);
+        order.push_back(key.to_string());
+    }
+
+    fn evict(&self) {
+        loop {
+            let over_budget = {
+                let entries = self.entries.borrow();
+                let total_bytes: u64 = entries.values().map(|e| e.bytes).sum();
+                entries.len() > self.max_entries.get() || total_bytes > self.max_bytes.get()
+            };
+            if !over_budget {
+                break;
+            }
+            let Some(lru_key) = self.order.borrow_mut().pop_front() else {
+                break;
+            };
+            self.entries.borrow_mut().remove(&lru_key);
+        }
+    }
+}
 
-/// Loads and caches shards from R2 with edge caching via Cache API.
-pub struct ShardLoader<'a> {
-    env: &'a Env,
-    bucket: Bucket,
-    cache: Cache,
+thread_local! {
+    /// Isolate-lifetime, outliving any single `ShardLoader`/request - a
+    /// warm isolate handling a burst of traffic reuses it across requests.
+    static DB_CACHE: DatabaseLru =
+        DatabaseLru::new(DEFAULT_DB_CACHE_MAX_ENTRIES, DEFAULT_DB_CACHE_MAX_BYTES);
+}
+
+/// Loads and caches shards from R2, fronted by a swappable [`ShardBucket`]
+/// and [`ShardCache`].
+pub struct ShardLoader {
+    bucket: Box<dyn ShardBucket>,
+    cache: Box<dyn ShardCache>,
+    /// Cap on cumulative shard bytes fetched per `search`/`reverse_geocode`
+    /// call; optional (non-HEAD) shards are skipped once it's exceeded.
+    byte_budget: u64,
+    /// Counters for the in-flight `search`/`reverse_geocode` call - reset
+    /// at the start of each one.
+    stats: RefCell<CacheStats>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -36,7 +194,6 @@ struct StacLink {
 #[derive(Debug, Deserialize)]
 struct EmbeddedItem {
     record_count: u64,
-    #[allow(dead_code)]
     size_bytes: u64,
     #[allow(dead_code)]
     sha256: String,
@@ -66,7 +223,6 @@ struct StacItem {
 #[derive(Debug, Deserialize)]
 struct StacItemProperties {
     record_count: u64,
-    #[allow(dead_code)]
     size_bytes: u64,
     #[allow(dead_code)]
     sha256: String,
@@ -82,45 +238,85 @@ struct StacAsset {
     href: String,
 }
 
-impl<'a> ShardLoader<'a> {
-    pub fn new(env: &'a Env) -> Result<Self> {
+impl ShardLoader {
+    pub fn new(env: &Env) -> Result<Self> {
+        Self::with_cache(env, Box::new(CloudflareCache::new()))
+    }
+
+    /// Build a `ShardLoader` with an explicit cache backend - e.g. a
+    /// `DummyCache` in tests run off-platform.
+    pub fn with_cache(env: &Env, cache: Box<dyn ShardCache>) -> Result<Self> {
         let bucket = env.bucket("SHARDS_BUCKET")?;
-        let cache = Cache::default();
-        Ok(Self { env, bucket, cache })
+        let byte_budget = env
+            .var(SHARD_BYTE_BUDGET_VAR)
+            .ok()
+            .and_then(|v| v.to_string().parse::<u64>().ok())
+            .unwrap_or(DEFAULT_SHARD_BYTE_BUDGET);
+
+        let db_cache_max_entries = env
+            .var(DB_CACHE_MAX_ENTRIES_VAR)
+            .ok()
+            .and_then(|v| v.to_string().parse::<usize>().ok())
+            .unwrap_or(DEFAULT_DB_CACHE_MAX_ENTRIES);
+        let db_cache_max_bytes = env
+            .var(DB_CACHE_MAX_BYTES_VAR)
+            .ok()
+            .and_then(|v| v.to_string().parse::<u64>().ok())
+            .unwrap_or(DEFAULT_DB_CACHE_MAX_BYTES);
+        DB_CACHE.with(|c| c.configure(db_cache_max_entries, db_cache_max_bytes));
+
+        Ok(Self::from_parts(
+            Box::new(R2ShardBucket::new(bucket)),
+            cache,
+            byte_budget,
+        ))
     }
 
-    /// Fetch from R2 with edge caching via Cache API.
-    async fn cached_get(&self, key: &str, ttl: u64) -> Result<Option<Vec<u8>>> {
-        let cache_key = format!("{}{}", CACHE_PREFIX, key);
+    /// Build a `ShardLoader` directly from a bucket and cache backend, with
+    /// no live `worker::Env` required - e.g. an `InMemoryBucket`/`DummyCache`
+    /// pair in tests, so the byte budget, `CacheStats`, and isolate-lifetime
+    /// `DatabaseLru` this struct drives can be exercised under `cargo test`
+    /// against synthetic shards, off the Workers runtime.
+    pub fn from_parts(bucket: Box<dyn ShardBucket>, cache: Box<dyn ShardCache>, byte_budget: u64) -> Self {
+        Self {
+            bucket,
+            cache,
+            byte_budget,
+            stats: RefCell::new(CacheStats::default()),
+        }
+    }
 
-        // Try cache first
-        let request = Request::new(&cache_key, Method::Get)?;
-        if let Some(mut response) = self.cache.get(&request, false).await? {
-            console_log!("Cache HIT: {}", key);
-            let bytes = response.bytes().await?;
+    /// Reset the per-request counters; call once at the start of each
+    /// `search`/`reverse_geocode` invocation.
+    fn reset_stats(&self) {
+        *self.stats.borrow_mut() = CacheStats::default();
+    }
+
+    /// Hit/miss and egress counters accumulated since the last `search` or
+    /// `reverse_geocode` call.
+    pub fn cache_stats(&self) -> CacheStats {
+        self.stats.borrow().clone()
+    }
+
+    /// Bytes remaining in the current request's fetch budget.
+    fn remaining_budget(&self) -> u64 {
+        self.byte_budget.saturating_sub(self.stats.borrow().bytes_loaded())
+    }
+
+    /// Fetch from R2, fronted by the configured cache backend.
+    async fn cached_get(&self, key: &str, ttl: u64) -> Result<Option<Vec<u8>>> {
+        if let Some(bytes) = self.cache.get(key).await? {
+            let mut stats = self.stats.borrow_mut();
+            stats.cache_hits += 1;
+            stats.bytes_from_cache += bytes.len() as u64;
             return Ok(Some(bytes));
         }
-
-        console_log!("Cache MISS: {}", key);
+        self.stats.borrow_mut().cache_misses += 1;
 
         // Fetch from R2
-        let obj = self.bucket.get(key).execute().await?;
-        if let Some(obj) = obj {
-            let body = obj.body().ok_or_else(|| Error::RustError("Empty object".into()))?;
-            let bytes = body.bytes().await?;
-
-            // Store in cache with TTL (non-blocking via waitUntil would be ideal, but for now inline)
-            let headers = Headers::new();
-            headers.set("Cache-Control", &format!("s-maxage={}", ttl))?;
-            headers.set("Content-Type", "application/octet-stream")?;
-
-            let cache_response = Response::from_bytes(bytes.clone())?.with_headers(headers);
-            let cache_request = Request::new(&cache_key, Method::Get)?;
-
-            // Put in cache (best effort, don't fail the request if caching fails)
-            if let Err(e) = self.cache.put(&cache_request, cache_response).await {
-                console_log!("Cache PUT failed for {}: {:?}", key, e);
-            }
+        if let Some(bytes) = self.bucket.get(key).await? {
+            self.cache.put(key, bytes.clone(), ttl).await?;
+            self.stats.borrow_mut().bytes_from_r2 += bytes.len() as u64;
 
             return Ok(Some(bytes));
         }
@@ -140,24 +336,55 @@ impl<'a> ShardLoader<'a> {
         }
     }
 
+    /// Load a STAC metadata document (catalog/collection), preferring a
+    /// `.cbor` sibling of `json_key` when present - same schema, smaller and
+    /// cheaper to parse than JSON - and falling back to the JSON original
+    /// for older catalogs that were never re-encoded.
+    async fn load_stac_metadata<T: serde::de::DeserializeOwned>(
+        &self,
+        json_key: &str,
+        ttl: u64,
+    ) -> Result<T> {
+        let cbor_key = json_key.replace(".json", ".cbor");
+        if let Some(bytes) = self.cached_get(&cbor_key, ttl).await? {
+            return ciborium::de::from_reader(bytes.as_slice())
+                .map_err(|e| Error::RustError(format!("Failed to parse {}: {}", cbor_key, e)));
+        }
+
+        let text = self
+            .cached_get_text(json_key, ttl)
+            .await?
+            .ok_or_else(|| Error::RustError(format!("{} not found", json_key)))?;
+
+        serde_json::from_str(&text)
+            .map_err(|e| Error::RustError(format!("Failed to parse {}: {}", json_key, e)))
+    }
+
     /// Search across HEAD and country shards.
     pub async fn search(
         &self,
         query: &GeocoderQuery,
         cf_country: Option<&str>,
     ) -> Result<Vec<GeocoderResult>> {
+        self.reset_stats();
+
         // Load STAC catalog to find shards
         let catalog = self.load_catalog().await?;
         let (version, collection) = self.load_latest_collection(&catalog).await?;
 
         // Query HEAD shard (required - fail if unavailable)
-        let head_results = self.query_shard(&version, "HEAD", &collection, query).await?;
+        let head_results = self
+            .query_shard(&version, "HEAD", &collection, query, true)
+            .await?;
         let mut all_results = head_results;
 
         // Query country shard if available (optional - log errors but continue)
         if let Some(country) = cf_country {
             if self.collection_has_shard(&collection, country) {
-                match self.query_shard(&version, country, &collection, query).await {
+                match self
+                    .query_shard(&version, country, &collection, query, false)
+                    .await
+                {
                     Ok(results) => all_results.extend(results),
                     Err(e) => {
                         console_log!("Warning: country shard {} unavailable: {:?}", country, e);
@@ -177,6 +404,12 @@ impl<'a> ShardLoader<'a> {
         let mut seen = std::collections::HashSet::new();
         all_results.retain(|r| seen.insert(r.gers_id.clone()));
 
+        // Merge near-identical hits (same name, population-derived radius
+        // overlap) - collapses the common "Springfield, Springfield
+        // (township), Springfield (neighborhood)" clutter that a plain
+        // gers_id dedup above doesn't catch.
+        let mut all_results = merge_results(all_results);
+
         // Apply location bias (can elevate results from country shard)
         if !matches!(query.bias, LocationBias::None) {
             apply_location_bias(&mut all_results, &query.bias);
@@ -195,13 +428,18 @@ impl<'a> ShardLoader<'a> {
         lon: f64,
         cf_country: Option<&str>,
     ) -> Result<Option<ReverseResult>> {
+        self.reset_stats();
+
         // Load STAC catalog to find reverse shards
         let catalog = self.load_catalog().await?;
         let (version, _collection) = self.load_latest_collection(&catalog).await?;
 
         // Try country shard first if available (more specific data)
         if let Some(country) = cf_country {
-            match self.query_reverse_shard(&version, country, lat, lon).await {
+            match self
+                .query_reverse_shard(&version, country, lat, lon, false)
+                .await
+            {
                 Ok(Some(result)) => return Ok(Some(result)),
                 Ok(None) => {
                     console_log!("No result in country {} reverse shard", country);
@@ -213,16 +451,22 @@ impl<'a> ShardLoader<'a> {
         }
 
         // Fall back to HEAD shard
-        self.query_reverse_shard(&version, "HEAD", lat, lon).await
+        self.query_reverse_shard(&version, "HEAD", lat, lon, true)
+            .await
     }
 
+    /// `required` disables the fetch-budget check - the HEAD shard has no
+    /// further fallback, so it's always worth fetching in full.
     async fn query_reverse_shard(
         &self,
         version: &str,
         shard_id: &str,
         lat: f64,
         lon: f64,
+        required: bool,
     ) -> Result<Option<ReverseResult>> {
+        self.stats.borrow_mut().record_shard_query(shard_id);
+
         // Load the reverse shard item metadata (cached)
         let item_key = format!("{}/reverse-items/{}.json", version, shard_id);
         let item_text = self
@@ -237,6 +481,42 @@ impl<'a> ShardLoader<'a> {
         let shard_href = &item.assets.data.href;
         let shard_key = format!("{}/{}", version, shard_href.trim_start_matches("./"));
 
+        // Already fully opened earlier this isolate's lifetime - skip the
+        // budget check, the paged attempt, and the Cache API entirely.
+        if let Some(db) = DB_CACHE.with(|c| c.get(&shard_key)) {
+            self.stats.borrow_mut().db_cache_hits += 1;
+            return db
+                .reverse(lat, lon, Some(1))
+                .map(|results| results.into_iter().next())
+                .map_err(|e| Error::RustError(format!("Reverse geocode failed: {}", e)));
+        }
+
+        if !required && item.properties.size_bytes > self.remaining_budget() {
+            return Err(Error::RustError(format!(
+                "reverse shard {} ({} bytes) would exceed the {} byte fetch budget ({} remaining)",
+                shard_id,
+                item.properties.size_bytes,
+                self.byte_budget,
+                self.remaining_budget()
+            )));
+        }
+
+        // A reverse lookup only ever touches a handful of b-tree pages, so
+        // try paging the shard on demand before paying for a whole download.
+        match self
+            .query_reverse_shard_paged(&shard_key, item.properties.size_bytes, lat, lon)
+            .await
+        {
+            Ok(result) => return Ok(result),
+            Err(e) => {
+                console_log!(
+                    "Paged read of {} unavailable, falling back to full download: {:?}",
+                    shard_key,
+                    e
+                );
+            }
+        }
+
         let shard_bytes = self
             .cached_get(&shard_key, SHARD_CACHE_TTL)
             .await?
@@ -252,22 +532,112 @@ impl<'a> ShardLoader<'a> {
         // Open the SQLite database from bytes and query it
         let db = Database::from_bytes(&shard_bytes)
             .map_err(|e| Error::RustError(format!("Failed to open reverse shard database: {}", e)))?;
+        self.stats.borrow_mut().shards_opened += 1;
+        let db = DB_CACHE.with(|c| c.insert(shard_key, db, shard_bytes.len() as u64));
 
         let result = db
-            .reverse_geocode(lat, lon)
-            .map_err(|e| Error::RustError(format!("Reverse geocode failed: {}", e)))?;
+            .reverse(lat, lon, Some(1))
+            .map_err(|e| Error::RustError(format!("Reverse geocode failed: {}", e)))?
+            .into_iter()
+            .next();
 
         Ok(result)
     }
 
-    async fn load_catalog(&self) -> Result<StacCatalog> {
-        let text = self
-            .cached_get_text("catalog.json", CATALOG_CACHE_TTL)
-            .await?
-            .ok_or_else(|| Error::RustError("catalog.json not found".into()))?;
+    /// Attempt the lazy-paged read path: warm only the file header and the
+    /// schema/root page (page 1) up front, then let SQLite's own b-tree
+    /// traversal drive the fetching - each time it touches a page
+    /// `R2PageReader` hasn't warmed, `op` fails, we fetch just the page it
+    /// faulted on, and retry. So a query costs only the pages its own plan
+    /// actually touches, not a guessed prefix. Gives up after
+    /// `MAX_PAGE_FAULTS` round-trips and lets the caller fall back to a
+    /// whole-shard download. Shared by `query_reverse_shard`/`query_shard`'s
+    /// paged attempts; never caches the `Database` it opens - it's only
+    /// ever warmed for the pages this one call touched (see `DatabaseLru`'s
+    /// doc comment).
+    async fn query_paged<T>(
+        &self,
+        op_label: &str,
+        shard_key: &str,
+        file_size: u64,
+        op: impl Fn(&Database) -> geocoder_core::Result<T>,
+    ) -> Result<T> {
+        let reader = R2PageReader::new(shard_key.to_string(), file_size);
+        reader.warm(self.bucket.as_ref(), 0, SQLITE_HEADER_SIZE).await?;
 
-        serde_json::from_str(&text)
-            .map_err(|e| Error::RustError(format!("Failed to parse catalog: {}", e)))
+        let header_bytes = reader
+            .read_range(0, SQLITE_HEADER_SIZE)
+            .map_err(|e| Error::RustError(e.to_string()))?;
+        let header =
+            SqliteHeader::parse(&header_bytes).map_err(|e| Error::RustError(e.to_string()))?;
+
+        let (offset, length) = header.page_range(1);
+        reader.warm(self.bucket.as_ref(), offset, length).await?;
+
+        let db = Database::from_pager(reader.clone())
+            .map_err(|e| Error::RustError(format!("Failed to open paged shard: {}", e)))?;
+        self.stats.borrow_mut().shards_opened += 1;
+
+        for _ in 0..MAX_PAGE_FAULTS {
+            match op(&db) {
+                Ok(value) => return Ok(value),
+                Err(e) => {
+                    let Some((offset, length)) = reader.take_page_fault() else {
+                        return Err(Error::RustError(format!("{} failed: {}", op_label, e)));
+                    };
+                    reader.warm(self.bucket.as_ref(), offset, length).await?;
+                }
+            }
+        }
+
+        Err(Error::RustError(format!(
+            "paged read of {} exceeded {} page faults",
+            shard_key, MAX_PAGE_FAULTS
+        )))
+    }
+
+    /// Paged attempt for a reverse lookup - only ever touches a handful of
+    /// b-tree pages, so this is almost always cheaper than a whole download.
+    async fn query_reverse_shard_paged(
+        &self,
+        shard_key: &str,
+        file_size: u64,
+        lat: f64,
+        lon: f64,
+    ) -> Result<Option<ReverseResult>> {
+        self.query_paged("Paged reverse geocode", shard_key, file_size, |db| {
+            db.reverse(lat, lon, Some(1))
+        })
+        .await
+        .map(|results| results.into_iter().next())
+    }
+
+    /// Paged attempt for a forward search - touches whatever pages the
+    /// FTS5/divisions query plan visits, which can be more than a point
+    /// lookup for a broad query term; if that exceeds `MAX_PAGE_FAULTS`,
+    /// `query_shard` falls back to a whole-shard download like any other
+    /// paging failure.
+    async fn query_shard_paged(
+        &self,
+        shard_key: &str,
+        file_size: u64,
+        query: &GeocoderQuery,
+    ) -> Result<Vec<GeocoderResult>> {
+        let response = self
+            .query_paged("Paged search", shard_key, file_size, |db| db.search(query))
+            .await?;
+        if response.degraded {
+            console_log!(
+                "Paged search for shard {} hit its time budget; results may be incomplete",
+                shard_key
+            );
+        }
+        Ok(response.results)
+    }
+
+    async fn load_catalog(&self) -> Result<StacCatalog> {
+        self.load_stac_metadata("catalog.json", CATALOG_CACHE_TTL)
+            .await
     }
 
     /// Load the latest collection and return it along with its version string.
@@ -289,13 +659,7 @@ impl<'a> ShardLoader<'a> {
             .to_string();
 
         let key = format!("{}/collection.json", version);
-        let text = self
-            .cached_get_text(&key, COLLECTION_CACHE_TTL)
-            .await?
-            .ok_or_else(|| Error::RustError(format!("{} not found", key)))?;
-
-        let collection: StacCollection = serde_json::from_str(&text)
-            .map_err(|e| Error::RustError(format!("Failed to parse collection: {}", e)))?;
+        let collection = self.load_stac_metadata(&key, COLLECTION_CACHE_TTL).await?;
 
         Ok((version, collection))
     }
@@ -317,33 +681,74 @@ impl<'a> ShardLoader<'a> {
         collection.items.get(shard_id)
     }
 
+    /// `required` disables the fetch-budget check - the HEAD shard is
+    /// always fetched in full, even over budget, since there's no fallback.
     async fn query_shard(
         &self,
         version: &str,
         shard_id: &str,
         collection: &StacCollection,
         query: &GeocoderQuery,
+        required: bool,
     ) -> Result<Vec<GeocoderResult>> {
+        self.stats.borrow_mut().record_shard_query(shard_id);
+
         // Get item metadata from embedded items (new format) or fall back to separate file
-        let (shard_href, record_count) = if let Some(item) = self.get_embedded_item(collection, shard_id) {
-            (item.href.clone(), item.record_count)
-        } else {
-            // Legacy: load from separate item file
-            let item_key = format!("{}/items/{}.json", version, shard_id);
-            let item_text = self
-                .cached_get_text(&item_key, SHARD_CACHE_TTL)
-                .await?
-                .ok_or_else(|| Error::RustError(format!("Item {} not found", item_key)))?;
-
-            let item: StacItem = serde_json::from_str(&item_text)
-                .map_err(|e| Error::RustError(format!("Failed to parse item: {}", e)))?;
-
-            (item.assets.data.href.clone(), item.properties.record_count)
-        };
+        let (shard_href, record_count, size_bytes) =
+            if let Some(item) = self.get_embedded_item(collection, shard_id) {
+                (item.href.clone(), item.record_count, item.size_bytes)
+            } else {
+                // Legacy: load from separate item file
+                let item_key = format!("{}/items/{}.json", version, shard_id);
+                let item_text = self
+                    .cached_get_text(&item_key, SHARD_CACHE_TTL)
+                    .await?
+                    .ok_or_else(|| Error::RustError(format!("Item {} not found", item_key)))?;
+
+                let item: StacItem = serde_json::from_str(&item_text)
+                    .map_err(|e| Error::RustError(format!("Failed to parse item: {}", e)))?;
+
+                (
+                    item.assets.data.href.clone(),
+                    item.properties.record_count,
+                    item.properties.size_bytes,
+                )
+            };
 
         // Load the actual shard database (cached)
         let shard_key = format!("{}/{}", version, shard_href.trim_start_matches("./"));
 
+        // Already fully opened earlier this isolate's lifetime - skip the
+        // budget check, the paged attempt, and the Cache API entirely.
+        if let Some(db) = DB_CACHE.with(|c| c.get(&shard_key)) {
+            self.stats.borrow_mut().db_cache_hits += 1;
+            return self.finish_search(&db, shard_id, query);
+        }
+
+        if !required && size_bytes > self.remaining_budget() {
+            return Err(Error::RustError(format!(
+                "shard {} ({} bytes) would exceed the {} byte fetch budget ({} remaining)",
+                shard_id,
+                size_bytes,
+                self.byte_budget,
+                self.remaining_budget()
+            )));
+        }
+
+        // A search only ever touches the FTS5/divisions pages its own query
+        // plan visits, so try paging the shard on demand before paying for
+        // a whole download - same tradeoff as query_reverse_shard.
+        match self.query_shard_paged(&shard_key, size_bytes, query).await {
+            Ok(results) => return Ok(results),
+            Err(e) => {
+                console_log!(
+                    "Paged read of {} unavailable, falling back to full download: {:?}",
+                    shard_key,
+                    e
+                );
+            }
+        }
+
         let shard_bytes = self
             .cached_get(&shard_key, SHARD_CACHE_TTL)
             .await?
@@ -359,11 +764,282 @@ impl<'a> ShardLoader<'a> {
         // Open the SQLite database from bytes and query it
         let db = Database::from_bytes(&shard_bytes)
             .map_err(|e| Error::RustError(format!("Failed to open shard database: {}", e)))?;
+        self.stats.borrow_mut().shards_opened += 1;
+        let db = DB_CACHE.with(|c| c.insert(shard_key, db, shard_bytes.len() as u64));
+
+        self.finish_search(&db, shard_id, query)
+    }
 
-        let results = db
+    fn finish_search(
+        &self,
+        db: &Database,
+        shard_id: &str,
+        query: &GeocoderQuery,
+    ) -> Result<Vec<GeocoderResult>> {
+        let response = db
             .search(query)
             .map_err(|e| Error::RustError(format!("Search failed: {}", e)))?;
+        if response.degraded {
+            console_log!("Search for shard {} hit its time budget; results may be incomplete", shard_id);
+        }
+
+        Ok(response.results)
+    }
+}
+
+// `ShardLoader::new`/`with_cache` can only be built from a live `worker::Env`,
+// so these tests go through `ShardLoader::from_parts` against an
+// `InMemoryBucket`/`DummyCache` pair instead - the whole point of splitting
+// `ShardBucket`/`ShardCache` out from `Env` in the first place.
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bucket::InMemoryBucket;
+    use crate::cache::DummyCache;
+    use rusqlite::Connection;
+
+    const SHARD_SCHEMA: &str = "
+        CREATE TABLE divisions (
+            gers_id TEXT,
+            type TEXT,
+            primary_name TEXT,
+            lat REAL,
+            lon REAL,
+            bbox_xmin REAL,
+            bbox_ymin REAL,
+            bbox_xmax REAL,
+            bbox_ymax REAL,
+            population INTEGER,
+            country TEXT,
+            region TEXT
+        );
+        CREATE VIRTUAL TABLE divisions_fts USING fts5(
+            primary_name, content='divisions', content_rowid='rowid'
+        );
+        CREATE TABLE divisions_reverse (
+            gers_id TEXT,
+            subtype TEXT,
+            primary_name TEXT,
+            lat REAL,
+            lon REAL,
+            bbox_xmin REAL,
+            bbox_ymin REAL,
+            bbox_xmax REAL,
+            bbox_ymax REAL,
+            area REAL,
+            population INTEGER,
+            country TEXT,
+            region TEXT
+        );
+    ";
+
+    /// Build a tiny on-disk shard containing one division, searchable and
+    /// reverse-geocodable - same technique `Database::from_bytes` itself
+    /// uses internally to round-trip through a real SQLite file.
+    fn build_shard_bytes(gers_id: &str, name: &str, lat: f64, lon: f64) -> Vec<u8> {
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
+        let path = std::env::temp_dir().join(format!("shardloader-test-{:032x}.db", nanos));
+
+        let conn = Connection::open(&path).unwrap();
+        conn.execute_batch(SHARD_SCHEMA).unwrap();
+        conn.execute(
+            "INSERT INTO divisions
+                (gers_id, type, primary_name, lat, lon,
+                 bbox_xmin, bbox_ymin, bbox_xmax, bbox_ymax, population, country, region)
+             VALUES (?1, 'locality', ?2, ?3, ?4, ?4, ?3, ?4, ?3, 50000, 'US', 'IL')",
+            rusqlite::params![gers_id, name, lat, lon],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO divisions_fts(rowid, primary_name) SELECT rowid, primary_name FROM divisions",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO divisions_reverse
+                (gers_id, subtype, primary_name, lat, lon,
+                 bbox_xmin, bbox_ymin, bbox_xmax, bbox_ymax, area, population, country, region)
+             VALUES (?1, 'locality', ?2, ?3, ?4, ?4, ?3, ?4, ?3, 100.0, 50000, 'US', 'IL')",
+            rusqlite::params![gers_id, name, lat, lon],
+        )
+        .unwrap();
+        drop(conn);
+
+        let bytes = std::fs::read(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        bytes
+    }
+
+    /// `InMemoryBucket`'s/`DummyCache`'s futures never actually suspend -
+    /// same no-suspend poll trick as `cache::tests::block_on`.
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        use std::task::{Context, Poll, Wake, Waker};
+
+        struct NoopWake;
+        impl Wake for NoopWake {
+            fn wake(self: std::sync::Arc<Self>) {}
+        }
+
+        let waker = Waker::from(std::sync::Arc::new(NoopWake));
+        let mut cx = Context::from_waker(&waker);
+        let mut future = Box::pin(future);
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(value) => value,
+            Poll::Pending => panic!("ShardLoader future unexpectedly suspended"),
+        }
+    }
+
+    /// `DB_CACHE` is a process-wide `thread_local`, shared by every
+    /// `ShardLoader` on the same test-runner thread - so each test gets its
+    /// own version string to keep its shard keys from colliding with
+    /// another test's cached `Database`.
+    fn unique_version() -> String {
+        use std::sync::atomic::{AtomicU64, Ordering};
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        format!("test-{}", COUNTER.fetch_add(1, Ordering::Relaxed))
+    }
+
+    /// Seed `bucket` with a minimal STAC catalog/collection naming a HEAD
+    /// shard (and, if `country_shard` is given, an optional one under that
+    /// shard ID), plus a legacy reverse-item pointing at the same HEAD bytes.
+    fn seed_catalog(
+        bucket: &InMemoryBucket,
+        version: &str,
+        head_shard_bytes: Vec<u8>,
+        country_shard: Option<(&str, Vec<u8>, u64)>,
+    ) {
+        bucket.put(
+            "catalog.json",
+            format!(r#"{{"links":[{{"rel":"child","href":"./{version}/collection.json","latest":true}}]}}"#)
+                .into_bytes(),
+        );
+
+        let head_size = head_shard_bytes.len() as u64;
+        let country_item = country_shard.as_ref().map(|(id, _, size_bytes)| {
+            format!(
+                r#","{id}":{{"record_count":1,"size_bytes":{size_bytes},"sha256":"","href":"./shards/{id}.db"}}"#
+            )
+        });
+        bucket.put(
+            &format!("{version}/collection.json"),
+            format!(
+                r#"{{"id":"test","items":{{"HEAD":{{"record_count":1,"size_bytes":{head_size},"sha256":"","href":"./shards/HEAD.db"}}{}}},"links":[]}}"#,
+                country_item.unwrap_or_default()
+            )
+            .into_bytes(),
+        );
+
+        bucket.put(
+            &format!("{version}/reverse-items/HEAD.json"),
+            format!(
+                r#"{{"id":"HEAD","properties":{{"record_count":1,"size_bytes":{head_size},"sha256":""}},"assets":{{"data":{{"href":"./shards/HEAD.db"}}}}}}"#
+            )
+            .into_bytes(),
+        );
+        bucket.put(&format!("{version}/shards/HEAD.db"), head_shard_bytes);
+
+        if let Some((id, bytes, _)) = country_shard {
+            bucket.put(&format!("{version}/shards/{id}.db"), bytes);
+        }
+    }
+
+    #[test]
+    fn search_and_reverse_geocode_round_trip_through_the_shard_loader() {
+        let bucket = InMemoryBucket::new();
+        seed_catalog(
+            &bucket,
+            &unique_version(),
+            build_shard_bytes("springfield", "Springfield", 39.5, -89.6),
+            None,
+        );
+        let loader = ShardLoader::from_parts(
+            Box::new(bucket),
+            Box::new(DummyCache::new()),
+            DEFAULT_SHARD_BYTE_BUDGET,
+        );
+
+        let results = block_on(loader.search(&GeocoderQuery::new("springfield"), None)).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].primary_name, "Springfield");
+
+        let reverse = block_on(loader.reverse_geocode(39.5, -89.6, None)).unwrap();
+        assert_eq!(reverse.unwrap().primary_name, "Springfield");
+
+        let stats = loader.cache_stats();
+        assert_eq!(stats.shard_queries.get("HEAD"), Some(&1));
+    }
+
+    #[test]
+    fn a_second_lookup_against_the_same_shard_hits_the_isolate_db_cache() {
+        let bucket = InMemoryBucket::new();
+        seed_catalog(
+            &bucket,
+            &unique_version(),
+            build_shard_bytes("springfield", "Springfield", 39.5, -89.6),
+            None,
+        );
+        let loader = ShardLoader::from_parts(
+            Box::new(bucket),
+            Box::new(DummyCache::new()),
+            DEFAULT_SHARD_BYTE_BUDGET,
+        );
+
+        block_on(loader.search(&GeocoderQuery::new("springfield"), None)).unwrap();
+        block_on(loader.search(&GeocoderQuery::new("springfield"), None)).unwrap();
+
+        assert_eq!(loader.cache_stats().db_cache_hits, 1);
+    }
+
+    #[test]
+    fn optional_country_shard_is_skipped_over_budget_but_search_still_succeeds() {
+        let bucket = InMemoryBucket::new();
+        let country_bytes = build_shard_bytes("shelbyville", "Springfield", 10.0, 10.0);
+        // Declare the country shard as far bigger than the configured
+        // budget so `query_shard`'s budget check (not a fetch failure)
+        // is what skips it.
+        let inflated_size = DEFAULT_SHARD_BYTE_BUDGET * 2;
+        seed_catalog(
+            &bucket,
+            &unique_version(),
+            build_shard_bytes("springfield", "Springfield", 39.5, -89.6),
+            Some(("US", country_bytes, inflated_size)),
+        );
+        let loader = ShardLoader::from_parts(
+            Box::new(bucket),
+            Box::new(DummyCache::new()),
+            DEFAULT_SHARD_BYTE_BUDGET,
+        );
+
+        let results =
+            block_on(loader.search(&GeocoderQuery::new("springfield"), Some("US"))).unwrap();
+
+        // Only the HEAD hit - the country shard was over budget, not merged in.
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].gers_id, "springfield");
+    }
+
+    #[test]
+    fn search_fails_when_the_required_head_shard_is_missing() {
+        let version = unique_version();
+        let bucket = InMemoryBucket::new();
+        bucket.put(
+            "catalog.json",
+            format!(r#"{{"links":[{{"rel":"child","href":"./{version}/collection.json","latest":true}}]}}"#)
+                .into_bytes(),
+        );
+        bucket.put(
+            &format!("{version}/collection.json"),
+            br#"{"id":"test","items":{},"links":[]}"#.to_vec(),
+        );
+        let loader = ShardLoader::from_parts(
+            Box::new(bucket),
+            Box::new(DummyCache::new()),
+            DEFAULT_SHARD_BYTE_BUDGET,
+        );
 
-        Ok(results)
+        let err = block_on(loader.search(&GeocoderQuery::new("springfield"), None)).unwrap_err();
+        assert!(err.to_string().contains("not found"));
     }
 }